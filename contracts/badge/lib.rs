@@ -0,0 +1,357 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::badge::{
+    BadgeRef,
+    MINTER,
+};
+
+#[openbrush::implementation(PSP34, AccessControl, PSP34Metadata)]
+#[openbrush::contract]
+pub mod badge {
+
+    use ink::prelude::string::{
+        String,
+        ToString,
+    };
+
+    use ink::codegen::{
+        EmitEvent,
+        Env,
+    };
+
+    use openbrush::{
+        contracts::access_control::DEFAULT_ADMIN_ROLE,
+        traits::Storage,
+    };
+
+    #[ink(storage)]
+    #[derive(Default, Storage)]
+    pub struct Badge {
+        #[storage_field]
+        psp34: psp34::Data,
+
+        #[storage_field]
+        access: access_control::Data,
+
+        #[storage_field]
+        metadata: metadata::Data,
+    }
+
+    /// Role allowed to mint badges.
+    pub const MINTER: RoleType = ink::selector_id!("MINTER");
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+
+        #[ink(topic)]
+        to: Option<AccountId>,
+
+        #[ink(topic)]
+        id: Id,
+    }
+
+    #[overrider(psp34::Internal)]
+    fn _emit_transfer_event(
+        &self,
+        from: Option<AccountId>,
+        to: Option<AccountId>,
+        id: Id,
+    ) {
+        self.env().emit_event(Transfer { from, to, id });
+    }
+
+    // badges are soulbound, only mints get through
+    #[overrider(psp34::Internal)]
+    fn _before_token_transfer(
+        &mut self,
+        from: Option<&AccountId>,
+        _to: Option<&AccountId>,
+        _id: &Id,
+    ) -> Result<(), PSP34Error> {
+        if from.is_some() {
+            return Err(PSP34Error::Custom(String::from("Soulbound")))
+        }
+
+        Ok(())
+    }
+
+    #[overrider(PSP34)]
+    fn approve(
+        &mut self,
+        _operator: AccountId,
+        _id: Option<Id>,
+        _approved: bool,
+    ) -> Result<(), PSP34Error> {
+        Err(PSP34Error::Custom(String::from("Soulbound")))
+    }
+
+    impl Badge {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::new_with_metadata(
+                String::from("Magink Badge"),
+                String::from("MBDG"),
+                String::from("ipfs://magink-badges/"),
+            )
+        }
+
+        /// Creates a collection with the given name, symbol and base uri.
+        ///
+        /// The caller becomes the default admin and gets the `MINTER` role.
+        #[ink(constructor)]
+        pub fn new_with_metadata(name: String, symbol: String, base_uri: String) -> Self {
+            let mut _instance = Self::default();
+
+            let caller = Self::env().caller();
+            access_control::Internal::_init_with_admin(&mut _instance, Some(caller));
+            access_control::Internal::_setup_role(&mut _instance, MINTER, Some(caller));
+
+            let collection_id = psp34::PSP34Impl::collection_id(&_instance);
+
+            metadata::Internal::_set_attribute(
+                &mut _instance,
+                collection_id.clone(),
+                String::from("name"),
+                name,
+            );
+
+            metadata::Internal::_set_attribute(
+                &mut _instance,
+                collection_id.clone(),
+                String::from("symbol"),
+                symbol,
+            );
+
+            metadata::Internal::_set_attribute(
+                &mut _instance,
+                collection_id,
+                String::from("baseUri"),
+                base_uri,
+            );
+
+            _instance
+        }
+
+        /// Mints a badge of the lesson `lesson_id` to `to` and returns its id.
+        ///
+        /// The lesson id is kept in the `lessonId` attribute of the token.
+        #[ink(message)]
+        #[openbrush::modifiers(only_role(MINTER))]
+        pub fn mint_badge(
+            &mut self,
+            to: AccountId,
+            lesson_id: u32,
+        ) -> Result<Id, PSP34Error> {
+            // badges are never burnt, so the supply is the last id
+            let id = Id::U64(PSP34Impl::total_supply(self) as u64 + 1);
+
+            psp34::InternalImpl::_mint_to(self, to, id.clone())?;
+            metadata::Internal::_set_attribute(
+                self,
+                id.clone(),
+                String::from("lessonId"),
+                lesson_id.to_string(),
+            );
+
+            Ok(id)
+        }
+
+        /// Returns the lesson the badge `token_id` was minted for.
+        #[ink(message)]
+        pub fn lesson_of(&self, token_id: u64) -> Option<u32> {
+            PSP34MetadataImpl::get_attribute(
+                self,
+                Id::U64(token_id),
+                String::from("lessonId"),
+            )
+            .and_then(|lesson_id| lesson_id.parse().ok())
+        }
+
+        #[ink(message)]
+        pub fn get_total_supply(&self) -> Balance {
+            PSP34Impl::total_supply(self)
+        }
+
+        /// Returns the uri of the badge, shared by every badge of the same lesson.
+        #[ink(message)]
+        pub fn token_uri(&self, token_id: u64) -> Result<String, PSP34Error> {
+            let lesson_id = self.lesson_of(token_id).ok_or(PSP34Error::TokenNotExists)?;
+
+            let base_uri = PSP34MetadataImpl::get_attribute(
+                self,
+                PSP34Impl::collection_id(self),
+                String::from("baseUri"),
+            )
+            .unwrap_or_default();
+
+            Ok(base_uri + &lesson_id.to_string() + ".json")
+        }
+
+        #[ink(message)]
+        #[openbrush::modifiers(only_role(DEFAULT_ADMIN_ROLE))]
+        pub fn set_base_uri(&mut self, uri: String) -> Result<(), PSP34Error> {
+            let id = PSP34Impl::collection_id(self);
+            metadata::Internal::_set_attribute(self, id, String::from("baseUri"), uri);
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        use ink::env::test;
+
+        use openbrush::contracts::{
+            access_control::*,
+            psp34::*,
+        };
+
+        fn default_accounts() -> test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            test::default_accounts::<Environment>()
+        }
+
+        fn set_sender(sender: AccountId) {
+            ink::env::test::set_caller::<Environment>(sender);
+        }
+
+        #[ink::test]
+        fn new_with_metadata_works() {
+            let badge = Badge::new_with_metadata(
+                String::from("Sorcerer Badge"),
+                String::from("SRCB"),
+                String::from("ipfs://sorcerer-badges/"),
+            );
+            let collection_id = PSP34Impl::collection_id(&badge);
+
+            assert_eq!(
+                metadata::PSP34MetadataImpl::get_attribute(
+                    &badge,
+                    collection_id.clone(),
+                    String::from("name")
+                ),
+                Some(String::from("Sorcerer Badge"))
+            );
+
+            assert_eq!(
+                metadata::PSP34MetadataImpl::get_attribute(
+                    &badge,
+                    collection_id,
+                    String::from("symbol")
+                ),
+                Some(String::from("SRCB"))
+            );
+
+            let alice = Some(default_accounts().alice);
+            assert!(AccessControl::has_role(&badge, DEFAULT_ADMIN_ROLE, alice));
+            assert!(AccessControl::has_role(&badge, MINTER, alice));
+        }
+
+        #[ink::test]
+        fn mint_badge_works() {
+            let mut badge = Badge::new();
+            let accounts = default_accounts();
+
+            assert_eq!(badge.mint_badge(accounts.bob, 7), Ok(Id::U64(1)));
+            assert_eq!(badge.mint_badge(accounts.bob, 3), Ok(Id::U64(2)));
+            assert_eq!(badge.mint_badge(accounts.eve, 7), Ok(Id::U64(3)));
+
+            assert_eq!(PSP34Impl::owner_of(&badge, Id::U64(1)), Some(accounts.bob));
+            assert_eq!(PSP34Impl::owner_of(&badge, Id::U64(3)), Some(accounts.eve));
+            assert_eq!(badge.get_total_supply(), 3);
+
+            assert_eq!(badge.lesson_of(1), Some(7));
+            assert_eq!(badge.lesson_of(2), Some(3));
+            assert_eq!(badge.lesson_of(4), None);
+
+            assert_eq!(3, ink::env::test::recorded_events().count());
+        }
+
+        #[ink::test]
+        fn mint_badge_requires_minter_role() {
+            let mut badge = Badge::new();
+            let accounts = default_accounts();
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                badge.mint_badge(accounts.bob, 0),
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
+            );
+
+            set_sender(accounts.alice);
+            assert!(
+                AccessControl::grant_role(&mut badge, MINTER, Some(accounts.bob)).is_ok()
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(badge.mint_badge(accounts.eve, 0), Ok(Id::U64(1)));
+        }
+
+        #[ink::test]
+        fn transfer_fails() {
+            let mut badge = Badge::new();
+            let accounts = default_accounts();
+
+            assert_eq!(badge.mint_badge(accounts.alice, 0), Ok(Id::U64(1)));
+
+            assert_eq!(
+                PSP34::transfer(&mut badge, accounts.bob, Id::U64(1), vec![]),
+                Err(PSP34Error::Custom(String::from("Soulbound")))
+            );
+            assert_eq!(
+                PSP34Impl::owner_of(&badge, Id::U64(1)),
+                Some(accounts.alice)
+            );
+        }
+
+        #[ink::test]
+        fn approve_fails() {
+            let mut badge = Badge::new();
+            let accounts = default_accounts();
+
+            assert_eq!(badge.mint_badge(accounts.alice, 0), Ok(Id::U64(1)));
+
+            assert_eq!(
+                PSP34::approve(&mut badge, accounts.bob, Some(Id::U64(1)), true),
+                Err(PSP34Error::Custom(String::from("Soulbound")))
+            );
+            assert_eq!(
+                PSP34::approve(&mut badge, accounts.bob, None, true),
+                Err(PSP34Error::Custom(String::from("Soulbound")))
+            );
+            assert!(!PSP34::allowance(
+                &badge,
+                accounts.alice,
+                accounts.bob,
+                Some(Id::U64(1))
+            ));
+        }
+
+        #[ink::test]
+        fn token_uri_works() {
+            let mut badge = Badge::new();
+            let accounts = default_accounts();
+
+            assert_eq!(badge.mint_badge(accounts.bob, 7), Ok(Id::U64(1)));
+
+            assert_eq!(badge.token_uri(42), Err(PSP34Error::TokenNotExists));
+            assert_eq!(
+                badge.token_uri(1),
+                Ok(String::from("ipfs://magink-badges/7.json"))
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                badge.set_base_uri(String::from("ipfs://other/")),
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
+            );
+
+            set_sender(accounts.alice);
+            assert!(badge.set_base_uri(String::from("ipfs://other/")).is_ok());
+            assert_eq!(badge.token_uri(1), Ok(String::from("ipfs://other/7.json")));
+        }
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// Stand-in for the next Magink release, used by the upgrade e2e test.
+///
+/// It only knows the version of the stored data and leaves the rest of the Magink
+/// storage as it is, so the messages take `&self` and never write the root storage.
+#[ink::contract]
+pub mod magink_upgrade_fixture {
+    use magink::{
+        Error,
+        STORAGE_VERSION,
+        VERSION_KEY,
+    };
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MaginkUpgradeFixture {}
+
+    impl MaginkUpgradeFixture {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Bumps the stored data to the version after `STORAGE_VERSION`.
+        #[ink(message)]
+        pub fn migrate(&self) -> Result<(), Error> {
+            if self.get_version() > STORAGE_VERSION {
+                return Err(Error::AlreadyMigrated)
+            }
+
+            ink::env::set_contract_storage(&VERSION_KEY, &(STORAGE_VERSION + 1));
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_version(&self) -> u32 {
+            ink::env::get_contract_storage::<_, u32>(&VERSION_KEY)
+                .ok()
+                .flatten()
+                .unwrap_or_default()
+        }
+
+        /// Switches back to the code at `code_hash`.
+        #[ink(message)]
+        pub fn upgrade(&self, code_hash: Hash) -> Result<(), Error> {
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)
+        }
+    }
+}
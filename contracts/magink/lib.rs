@@ -1,41 +1,408 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::magink::{
+    Error,
+    MaginkRef,
+    STORAGE_VERSION,
+    VERSION_KEY,
+};
+
 #[allow(clippy::new_without_default)]
-#[ink::contract]
+#[openbrush::implementation(Ownable, Pausable)]
+#[openbrush::contract]
 pub mod magink {
     use crate::ensure;
 
-    use ink::storage::Mapping;
-
-    use ink::prelude::string::String;
+    use ink::{
+        prelude::{
+            string::String,
+            vec::Vec,
+        },
+        storage::{
+            traits::ManualKey,
+            Lazy,
+            Mapping,
+        },
+    };
 
-    use ink::env::{
-        call::{
-            build_call,
-            ExecutionInput,
-            Selector,
+    use ink::{
+        codegen::{
+            EmitEvent,
+            Env,
+            TraitCallBuilder,
         },
-        DefaultEnvironment,
+        env::{
+            call::FromAccountId,
+            hash::{
+                Blake2x256,
+                HashOutput,
+            },
+        },
+        ToAccountId,
     };
 
-    use openbrush::contracts::psp34::{
-        PSP34Error,
-        *,
+    use openbrush::{
+        contracts::{
+            ownable::OwnableError,
+            pausable::PausableError,
+            psp34::{
+                Id,
+                PSP34Error,
+            },
+        },
+        traits::Storage,
     };
 
+    use badge::BadgeRef;
+    use wizard::WizardRef;
+
+    /// Errors returned by every fallible Magink message.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
         TooEarlyToClaim,
         UserNotFound,
-        MintFailed,
         NotAllBadgesCollected,
+        AlreadyMinted,
+        /// The era length must be at least one block.
+        InvalidEraLength,
+        AlreadyStarted,
+        RestartNotConfirmed,
+        /// Every active lesson has been claimed already.
+        TooManyBadges,
+        /// The new code hash could not be set.
+        UpgradeFailed,
+        /// The storage is already at the current version.
+        AlreadyMigrated,
+        /// More accounts than `MAX_BATCH_SIZE` were queried at once.
+        BatchTooLarge,
+        /// There is no active lesson with the given id.
+        LessonNotFound,
+        /// The registry already holds `MAX_LESSONS` lessons.
+        TooManyLessons,
+        /// The new order is not a permutation of the active lessons.
+        InvalidLessonOrder,
+        /// The registry has no active lesson, so there is nothing to complete.
+        NoLessons,
+        /// The caller has already completed the lesson.
+        LessonAlreadyCompleted,
+        /// The lesson has a quiz, answer it with `commit_answer` and `reveal_answer`.
+        AnswerRequired,
+        /// A wrong answer locked the lesson for the caller, try again later.
+        LessonLocked,
+        /// The lesson has no quiz, claim it with `claim_lesson`.
+        NoQuiz,
+        /// The caller has no commitment for the lesson.
+        CommitmentNotFound,
+        /// The answer can only be revealed in a block after the commit.
+        RevealTooEarly,
+        /// The reveal window of the commitment is over, commit again.
+        CommitmentExpired,
+        /// The revealed answer and salt don't match the commitment.
+        CommitmentMismatch,
+        /// The reveal window must be at least one block.
+        InvalidRevealWindow,
+        Ownable(OwnableError),
+        /// The contract is paused, or not paused when unpausing.
+        Pausable(PausableError),
+        /// The wizard contract rejected the call.
+        Wizard(PSP34Error),
+        /// The call to the wizard or badge contract could not be executed.
+        CrossContractCallFailed,
+        /// There is no contract at the wizard address.
+        WizardNotFound,
+        /// The wizard contract trapped, e.g. it ran out of gas.
+        WizardTrapped,
+        /// The wizard contract reverted the call.
+        WizardReverted,
+        /// The wizard contract could not dispatch the call.
+        WizardLangError(ink::LangError),
+        /// The badge contract rejected the call, e.g. Magink lacks its `MINTER` role.
+        Badge(PSP34Error),
+        /// There is no contract at the badge address.
+        BadgeNotFound,
+        /// The badge contract trapped, e.g. it ran out of gas.
+        BadgeTrapped,
+        /// The badge contract reverted the call.
+        BadgeReverted,
+        /// The badge contract could not dispatch the call.
+        BadgeLangError(ink::LangError),
+    }
+
+    impl From<OwnableError> for Error {
+        fn from(error: OwnableError) -> Self {
+            Error::Ownable(error)
+        }
+    }
+
+    impl From<PausableError> for Error {
+        fn from(error: PausableError) -> Self {
+            Error::Pausable(error)
+        }
+    }
+
+    impl From<PSP34Error> for Error {
+        fn from(error: PSP34Error) -> Self {
+            Error::Wizard(error)
+        }
+    }
+
+    /// Unwraps the outcome of a call to the wizard contract.
+    fn wizard_call_result<T>(
+        result: Result<ink::MessageResult<T>, ink::env::Error>,
+    ) -> Result<T, Error> {
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(error)) => Err(Error::WizardLangError(error)),
+            Err(ink::env::Error::CodeNotFound | ink::env::Error::NotCallable) => {
+                Err(Error::WizardNotFound)
+            }
+            Err(ink::env::Error::CalleeTrapped) => Err(Error::WizardTrapped),
+            Err(ink::env::Error::CalleeReverted) => Err(Error::WizardReverted),
+            Err(_) => Err(Error::CrossContractCallFailed),
+        }
+    }
+
+    /// Returns the blake2 hash of `salt` followed by `answer`, as registered with
+    /// `set_quiz`.
+    pub fn answer_hash(salt: &Hash, answer: &str) -> Hash {
+        let mut input = Vec::with_capacity(salt.as_ref().len() + answer.len());
+        input.extend_from_slice(salt.as_ref());
+        input.extend_from_slice(answer.as_bytes());
+
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_bytes::<Blake2x256>(&input, &mut output);
+        Hash::from(output)
+    }
+
+    /// Returns the blake2 hash of `answer` followed by `salt` and `account`, as
+    /// committed with `commit_answer`.
+    pub fn commitment_hash(answer: &str, salt: &Hash, account: &AccountId) -> Hash {
+        let mut input = Vec::with_capacity(answer.len() + 64);
+        input.extend_from_slice(answer.as_bytes());
+        input.extend_from_slice(salt.as_ref());
+        input.extend_from_slice(account.as_ref());
+
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_bytes::<Blake2x256>(&input, &mut output);
+        Hash::from(output)
+    }
+
+    /// Unwraps the outcome of a call to the badge contract.
+    fn badge_call_result<T>(
+        result: Result<ink::MessageResult<T>, ink::env::Error>,
+    ) -> Result<T, Error> {
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(error)) => Err(Error::BadgeLangError(error)),
+            Err(ink::env::Error::CodeNotFound | ink::env::Error::NotCallable) => {
+                Err(Error::BadgeNotFound)
+            }
+            Err(ink::env::Error::CalleeTrapped) => Err(Error::BadgeTrapped),
+            Err(ink::env::Error::CalleeReverted) => Err(Error::BadgeReverted),
+            Err(_) => Err(Error::CrossContractCallFailed),
+        }
+    }
+
+    fn ensure_batch_size(accounts: &[AccountId]) -> Result<(), Error> {
+        ensure!(
+            accounts.len() <= MAX_BATCH_SIZE as usize,
+            Error::BatchTooLarge
+        );
+        Ok(())
+    }
+
+    /// Calls made by Magink to the wizard contract.
+    ///
+    /// Unit tests swap the deployed wizard for an in-memory one, since the
+    /// off-chain environment can't call other contracts.
+    pub trait WizardBackend {
+        /// Connects to the wizard, calls are limited to `gas_limit`.
+        fn connect(wizard: &WizardRef, gas_limit: u64) -> Self;
+
+        /// Returns the number of minted wizard tokens.
+        fn total_supply(&self) -> Result<Balance, Error>;
+
+        /// Returns the maximum number of wizard tokens.
+        fn max_supply(&self) -> Result<u64, Error>;
+
+        /// Mints the next free token to `to` and returns its id.
+        fn mint_next(&mut self, to: AccountId) -> Result<Id, Error>;
+    }
+
+    /// Calls the deployed wizard contract.
+    pub struct WizardCall {
+        wizard: WizardRef,
+        gas_limit: u64,
+    }
+
+    impl WizardBackend for WizardCall {
+        fn connect(wizard: &WizardRef, gas_limit: u64) -> Self {
+            Self {
+                wizard: wizard.clone(),
+                gas_limit,
+            }
+        }
+
+        fn total_supply(&self) -> Result<Balance, Error> {
+            wizard_call_result(
+                self.wizard
+                    .call()
+                    .get_total_supply()
+                    .gas_limit(self.gas_limit)
+                    .try_invoke(),
+            )
+        }
+
+        fn max_supply(&self) -> Result<u64, Error> {
+            wizard_call_result(
+                self.wizard
+                    .call()
+                    .max_supply()
+                    .gas_limit(self.gas_limit)
+                    .try_invoke(),
+            )
+        }
+
+        fn mint_next(&mut self, to: AccountId) -> Result<Id, Error> {
+            wizard_call_result(
+                self.wizard
+                    .call_mut()
+                    .mint_next(to)
+                    .gas_limit(self.gas_limit)
+                    .try_invoke(),
+            )?
+            .map_err(Error::from)
+        }
+    }
+
+    #[cfg(not(test))]
+    type WizardClient = WizardCall;
+
+    #[cfg(test)]
+    type WizardClient = mock::MockWizard;
+
+    /// Calls made by Magink to the badge contract, swapped in unit tests like
+    /// `WizardBackend`.
+    pub trait BadgeBackend {
+        /// Connects to the badge contract, calls are limited to `gas_limit`.
+        fn connect(badge: &BadgeRef, gas_limit: u64) -> Self;
+
+        /// Mints a badge of the lesson `lesson_id` to `to` and returns its id.
+        fn mint_badge(&mut self, to: AccountId, lesson_id: LessonId)
+            -> Result<Id, Error>;
+    }
+
+    /// Calls the deployed badge contract.
+    pub struct BadgeCall {
+        badge: BadgeRef,
+        gas_limit: u64,
+    }
+
+    impl BadgeBackend for BadgeCall {
+        fn connect(badge: &BadgeRef, gas_limit: u64) -> Self {
+            Self {
+                badge: badge.clone(),
+                gas_limit,
+            }
+        }
+
+        fn mint_badge(
+            &mut self,
+            to: AccountId,
+            lesson_id: LessonId,
+        ) -> Result<Id, Error> {
+            badge_call_result(
+                self.badge
+                    .call_mut()
+                    .mint_badge(to, lesson_id)
+                    .gas_limit(self.gas_limit)
+                    .try_invoke(),
+            )?
+            .map_err(Error::Badge)
+        }
     }
 
+    #[cfg(not(test))]
+    type BadgeClient = BadgeCall;
+
+    #[cfg(test)]
+    type BadgeClient = mock::MockBadge;
+
     #[ink(storage)]
+    #[derive(Storage)]
     pub struct Magink {
+        #[storage_field]
+        ownable: ownable::Data,
+
+        #[storage_field]
+        pausable: pausable::Data,
+
         user: Mapping<AccountId, Profile>,
-        wizard_contract_account_id: AccountId,
-        last_token_id: u64,
+        wizard: WizardRef,
+        minted: Mapping<AccountId, Id>,
+        era_length: BlockNumber,
+        wizard_gas_limit: u64,
+        version: Lazy<u32, ManualKey<VERSION_KEY>>,
+        lessons: Mapping<LessonId, Lesson>,
+        lesson_order: Vec<LessonId>,
+        next_lesson_id: LessonId,
+        quizzes: Mapping<LessonId, Quiz>,
+        answer_lockout: BlockNumber,
+        locked_until: Mapping<(AccountId, LessonId), BlockNumber>,
+        commitments: Mapping<(AccountId, LessonId), Commitment>,
+        reveal_window: BlockNumber,
+        badge: Option<BadgeRef>,
+        badge_tokens: Mapping<(AccountId, LessonId), Id>,
+        badge_gas_limit: u64,
+    }
+
+    pub type LessonId = u32;
+
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Lesson {
+        // id of the lesson, never reused
+        pub id: LessonId,
+
+        // hash of the lesson content kept off chain
+        pub content_hash: Hash,
+
+        pub title: String,
+
+        // retired lessons no longer count towards the badges
+        pub retired: bool,
+    }
+
+    /// Quiz gating the badge of a lesson.
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Quiz {
+        // public salt hashed in front of the answer
+        pub salt: Hash,
+
+        // `answer_hash(salt, answer)` of the right answer
+        pub answer_hash: Hash,
+    }
+
+    /// Answer committed to with `commit_answer`, waiting to be revealed.
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Commitment {
+        // `commitment_hash(answer, salt, account)`
+        pub hash: Hash,
+
+        // block number of the commit
+        pub block: BlockNumber,
     }
 
     #[derive(
@@ -47,386 +414,3689 @@ pub mod magink {
     )]
     pub struct Profile {
         // duration in blocks until next claim
-        claim_era: u8,
+        claim_era: BlockNumber,
 
         // block number of last claim
-        start_block: u32,
+        start_block: BlockNumber,
 
-        // number of badges claimed
+        // bit `id` is set once lesson `id` is completed
+        completed_lessons: u128,
+
+        // number of badges claimed before the last restart
+        previous_badges_claimed: u8,
+    }
+
+    impl Profile {
+        /// Returns the blocks left in the era at `current_block`.
+        fn remaining_at(&self, current_block: BlockNumber) -> BlockNumber {
+            let elapsed = current_block.saturating_sub(self.start_block);
+            self.claim_era.saturating_sub(elapsed)
+        }
+
+        /// Returns the first block at which the next badge can be claimed.
+        fn next_claim_block(&self) -> BlockNumber {
+            self.start_block.saturating_add(self.claim_era)
+        }
+
+        /// Returns the number of completed lessons.
+        fn badges_claimed(&self) -> u8 {
+            self.completed_lessons.count_ones() as u8
+        }
+
+        /// Returns true if the lesson with the given id is completed.
+        fn has_completed(&self, id: LessonId) -> bool {
+            self.completed_lessons & (1 << id) != 0
+        }
+
+        /// Returns the ids of the completed lessons, in ascending order.
+        fn completed_lessons(&self) -> Vec<LessonId> {
+            (0..MAX_LESSONS)
+                .filter(|id| self.has_completed(*id))
+                .collect()
+        }
+    }
+
+    /// Full state of an account, returned by `status`.
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Status {
+        // profile of the account, none before `start`
+        pub profile: Option<Profile>,
+
+        // blocks left until the next claim
+        pub remaining_blocks: BlockNumber,
+
+        // first block at which the next badge can be claimed
+        pub next_claim_block: Option<BlockNumber>,
+
+        // number of badges needed to mint the wizard
+        pub badges_required: u8,
+
+        // true if `mint_wizard` would mint right now
+        pub can_mint: bool,
+
+        // id of the wizard minted by the account
+        pub minted_token_id: Option<Id>,
+
+        // wizard tokens left to mint, none if the wizard could not be reached
+        pub remaining_supply: Option<u64>,
+    }
+
+    /// Emitted when an account starts the claiming era.
+    #[ink(event)]
+    pub struct Started {
+        #[ink(topic)]
+        account: AccountId,
+        era: BlockNumber,
+        start_block: BlockNumber,
+    }
+
+    /// Emitted when an account restarts the claiming era, dropping its badges.
+    #[ink(event)]
+    pub struct Restarted {
+        #[ink(topic)]
+        account: AccountId,
+        era: BlockNumber,
+        start_block: BlockNumber,
+        previous_badges_claimed: u8,
+    }
+
+    /// Emitted when an account claims a badge.
+    #[ink(event)]
+    pub struct BadgeClaimed {
+        #[ink(topic)]
+        account: AccountId,
+        lesson_id: LessonId,
         badges_claimed: u8,
+        block: BlockNumber,
+    }
+
+    /// Emitted when an account mints the Wizard NFT.
+    #[ink(event)]
+    pub struct WizardMinted {
+        #[ink(topic)]
+        account: AccountId,
+        token_id: Id,
+    }
+
+    /// Emitted when an account gets the badge token of a completed lesson.
+    #[ink(event)]
+    pub struct BadgeMinted {
+        #[ink(topic)]
+        account: AccountId,
+        lesson_id: LessonId,
+        token_id: Id,
+    }
+
+    /// Emitted when the owner adds a lesson.
+    #[ink(event)]
+    pub struct LessonAdded {
+        #[ink(topic)]
+        id: LessonId,
+        content_hash: Hash,
+    }
+
+    /// Emitted when the owner retires a lesson.
+    #[ink(event)]
+    pub struct LessonRetired {
+        #[ink(topic)]
+        id: LessonId,
+    }
+
+    /// Emitted when the owner reorders the active lessons.
+    #[ink(event)]
+    pub struct LessonsReordered {
+        order: Vec<LessonId>,
+    }
+
+    /// Emitted when the owner sets the quiz of a lesson.
+    #[ink(event)]
+    pub struct QuizSet {
+        #[ink(topic)]
+        lesson_id: LessonId,
+    }
+
+    /// Emitted when an account commits to an answer.
+    #[ink(event)]
+    pub struct AnswerCommitted {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        lesson_id: LessonId,
+        block: BlockNumber,
+    }
+
+    /// Emitted for every answer revealed to a quiz.
+    #[ink(event)]
+    pub struct AnswerSubmitted {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        lesson_id: LessonId,
+        correct: bool,
+        // set when a wrong answer locked the lesson
+        locked_until: Option<BlockNumber>,
+    }
+
+    /// Emitted when the owner pauses the contract.
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Emitted when the owner unpauses the contract.
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Emitted when the owner replaces the contract code.
+    #[ink(event)]
+    pub struct Upgraded {
+        code_hash: Hash,
+    }
+
+    /// Emitted when the storage is migrated to a new version.
+    #[ink(event)]
+    pub struct Migrated {
+        from_version: u32,
+        to_version: u32,
+    }
+
+    #[overrider(pausable::Internal)]
+    fn _emit_paused_event(&self, account: AccountId) {
+        self.env().emit_event(Paused { account });
+    }
+
+    #[overrider(pausable::Internal)]
+    fn _emit_unpaused_event(&self, account: AccountId) {
+        self.env().emit_event(Unpaused { account });
     }
 
+    /// Blocks between two claims by default, about a day of 12 second blocks.
+    pub const DEFAULT_ERA_LENGTH: BlockNumber = 7_200;
+
+    /// Gas limit of the calls to the wizard, zero forwards all the remaining gas.
+    pub const DEFAULT_WIZARD_GAS_LIMIT: u64 = 0;
+
+    /// Gas limit of the calls to the badge contract, zero forwards all the remaining
+    /// gas.
+    pub const DEFAULT_BADGE_GAS_LIMIT: u64 = 0;
+
+    /// Blocks a wrong answer locks a lesson for by default.
+    pub const DEFAULT_ANSWER_LOCKOUT: BlockNumber = 10;
+
+    /// Blocks after the commit block an answer can be revealed in by default.
+    pub const DEFAULT_REVEAL_WINDOW: BlockNumber = 20;
+
+    /// Version of the storage layout written by this code.
+    ///
+    /// Versioning starts at the first release: version 1 is the layout it ships
+    /// with, and layout changes before it need no migration. From then on, bump it
+    /// together with a migration step in `migrate` whenever the layout of the
+    /// stored data changes.
+    pub const STORAGE_VERSION: u32 = 1;
+
+    /// Storage key of the version, apart from the other fields so that code with
+    /// any layout can read it.
+    pub const VERSION_KEY: u32 = ink::selector_id!("magink::version");
+
+    /// Most accounts a batch query accepts.
+    pub const MAX_BATCH_SIZE: u32 = 100;
+
+    /// Most lessons the registry ever holds, retired ones included.
+    pub const MAX_LESSONS: u32 = 128;
+
     impl Magink {
         /// Creates a new Magink smart contract.
         #[ink(constructor)]
         pub fn new(account_id: AccountId) -> Self {
             // this place would be greate to transfer ownership of wizard to magink, but there is no onchain account exist at this moment
-            Self {
+            Self::init(<WizardRef as FromAccountId<Environment>>::from_account_id(
+                account_id,
+            ))
+        }
+
+        /// Instantiates a fresh Wizard collection from `wizard_code_hash` and wires it
+        /// to this contract.
+        ///
+        /// Magink is the instantiator, so it gets the `MINTER` role of the new Wizard
+        /// and can mint right away without a separate `grant_role` call. The
+        /// collection itself is administered by `wizard_admin`, usually the deployer.
+        #[ink(constructor)]
+        pub fn new_with_wizard(
+            wizard_code_hash: Hash,
+            max_supply: u64,
+            name: String,
+            symbol: String,
+            base_uri: String,
+            wizard_admin: AccountId,
+        ) -> Self {
+            let wizard = WizardRef::new_with_metadata(
+                max_supply,
+                name,
+                symbol,
+                base_uri,
+                wizard_admin,
+            )
+            .code_hash(wizard_code_hash)
+            .endowment(0)
+            .salt_bytes(Self::env().account_id())
+            .instantiate();
+
+            Self::init(wizard)
+        }
+
+        fn init(wizard: WizardRef) -> Self {
+            let mut instance = Self {
+                ownable: Default::default(),
+                pausable: Default::default(),
                 user: Mapping::new(),
-                wizard_contract_account_id: account_id,
-                last_token_id: 1,
-            }
+                wizard,
+                minted: Mapping::new(),
+                era_length: DEFAULT_ERA_LENGTH,
+                wizard_gas_limit: DEFAULT_WIZARD_GAS_LIMIT,
+                version: Lazy::new(),
+                lessons: Mapping::new(),
+                lesson_order: Vec::new(),
+                next_lesson_id: 0,
+                quizzes: Mapping::new(),
+                answer_lockout: DEFAULT_ANSWER_LOCKOUT,
+                locked_until: Mapping::new(),
+                commitments: Mapping::new(),
+                reveal_window: DEFAULT_REVEAL_WINDOW,
+                badge: None,
+                badge_tokens: Mapping::new(),
+                badge_gas_limit: DEFAULT_BADGE_GAS_LIMIT,
+            };
+
+            instance.version.set(&STORAGE_VERSION);
+            ownable::Internal::_init_with_owner(&mut instance, Self::env().caller());
+
+            instance
         }
 
         /// Total supply of wizard tokens
         #[ink(message)]
-        pub fn total_supply(&self) -> Balance {
-            build_call::<DefaultEnvironment>()
-                .call(self.wizard_contract_account_id)
-                .gas_limit(0)
-                .exec_input(ExecutionInput::new(Selector::new(ink::selector_bytes!(
-                    "get_total_supply"
-                ))))
-                .returns::<Balance>()
-                .invoke()
+        pub fn total_supply(&self) -> Result<Balance, Error> {
+            self.wizard_client().total_supply()
+        }
+
+        /// Start the Magink the claiming era for the caller.
+        ///
+        /// The era is the length configured by the owner. Fails if the caller has
+        /// already started, use `restart` to begin a new run.
+        #[ink(message)]
+        #[openbrush::modifiers(when_not_paused)]
+        pub fn start(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            ensure!(!self.user.contains(caller), Error::AlreadyStarted);
+
+            let era = self.era_length;
+            let start_block = self.env().block_number();
+            let profile = Profile {
+                claim_era: era,
+                start_block,
+                completed_lessons: 0,
+                previous_badges_claimed: 0,
+            };
+
+            self.user.insert(caller, &profile);
+
+            self.env().emit_event(Started {
+                account: caller,
+                era,
+                start_block,
+            });
+
+            Ok(())
         }
 
-        /// (Re)Start the Magink the claiming era for the caller.
+        /// Restart the claiming era for the caller with the current era length,
+        /// dropping the claimed badges.
+        ///
+        /// `confirm_badges` must match the caller's current badge count to confirm
+        /// giving them up. The count is kept in the profile as the previous run.
         #[ink(message)]
-        pub fn start(&mut self, era: u8) {
+        #[openbrush::modifiers(when_not_paused)]
+        pub fn restart(&mut self, confirm_badges: u8) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let profile = self.user.get(caller).ok_or(Error::UserNotFound)?;
+            ensure!(
+                profile.badges_claimed() == confirm_badges,
+                Error::RestartNotConfirmed
+            );
+
+            let era = self.era_length;
+            let start_block = self.env().block_number();
             let profile = Profile {
                 claim_era: era,
-                start_block: self.env().block_number(),
-                badges_claimed: 0,
+                start_block,
+                completed_lessons: 0,
+                previous_badges_claimed: profile.badges_claimed(),
             };
 
-            self.user.insert(self.env().caller(), &profile);
+            self.user.insert(caller, &profile);
+
+            self.env().emit_event(Restarted {
+                account: caller,
+                era,
+                start_block,
+                previous_badges_claimed: profile.previous_badges_claimed,
+            });
+
+            Ok(())
         }
 
         /// Claim the badge after the era.
+        ///
+        /// Completes the first active lesson, in the registry order, the caller
+        /// hasn't completed yet. Fails if that lesson has a quiz.
         #[ink(message)]
+        #[openbrush::modifiers(when_not_paused)]
         pub fn claim(&mut self) -> Result<(), Error> {
-            ensure!(self.get_remaining() == 0, Error::TooEarlyToClaim);
+            let caller = self.env().caller();
+            let profile = self.user.get(caller).ok_or(Error::UserNotFound)?;
 
-            // update profile
-            let mut profile = self.get_profile().ok_or(Error::UserNotFound).unwrap();
+            let lesson_id = *self
+                .lesson_order
+                .iter()
+                .find(|id| !profile.has_completed(**id))
+                .ok_or(Error::TooManyBadges)?;
+            ensure!(!self.quizzes.contains(lesson_id), Error::AnswerRequired);
 
-            profile.badges_claimed += 1;
-            profile.start_block = self.env().block_number();
+            self.complete_lesson(caller, profile, lesson_id)
+        }
 
-            self.user.insert(self.env().caller(), &profile);
+        /// Claim the badge of the given lesson after the era.
+        ///
+        /// Fails if the lesson has a quiz, use `commit_answer` and `reveal_answer`.
+        #[ink(message)]
+        #[openbrush::modifiers(when_not_paused)]
+        pub fn claim_lesson(&mut self, lesson_id: LessonId) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let profile = self.user.get(caller).ok_or(Error::UserNotFound)?;
+            self.ensure_can_complete(&profile, lesson_id)?;
+            ensure!(!self.quizzes.contains(lesson_id), Error::AnswerRequired);
 
-            Ok(())
+            self.complete_lesson(caller, profile, lesson_id)
         }
 
-        /// Mint Wizard NFT
+        /// Commits to an answer to the quiz of the given lesson, replacing any
+        /// previous commitment.
+        ///
+        /// `commitment` is `commitment_hash(answer, salt, caller)`, with `salt` a
+        /// secret of the caller. The answer is revealed with `reveal_answer` in a
+        /// later block, so it can't be copied from a pending transaction.
         #[ink(message)]
-        pub fn mint_wizard(&mut self) -> Result<(), PSP34Error> {
-            // assuming that exact number is configured in UI part
+        #[openbrush::modifiers(when_not_paused)]
+        pub fn commit_answer(
+            &mut self,
+            lesson_id: LessonId,
+            commitment: Hash,
+        ) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let profile = self.user.get(caller).ok_or(Error::UserNotFound)?;
+            self.ensure_can_complete(&profile, lesson_id)?;
+            ensure!(self.quizzes.contains(lesson_id), Error::NoQuiz);
             ensure!(
-                self.get_badges() > 0,
-                PSP34Error::Custom(String::from("NotAllBadgesCollected"))
+                self.get_locked_until(caller, lesson_id).is_none(),
+                Error::LessonLocked
             );
 
-            let caller = self.env().caller();
-            build_call::<DefaultEnvironment>()
-                .call(self.wizard_contract_account_id)
-                .gas_limit(0)
-                .exec_input(
-                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
-                        "PSP34Mintable::mint"
-                    )))
-                    .push_arg(caller)
-                    .push_arg(Id::U64(self.last_token_id)),
-                )
-                .returns::<Result<(), PSP34Error>>()
-                .invoke()?;
-
-            self.last_token_id += 1;
+            let block = self.env().block_number();
+            self.commitments.insert(
+                (caller, lesson_id),
+                &Commitment {
+                    hash: commitment,
+                    block,
+                },
+            );
+
+            self.env().emit_event(AnswerCommitted {
+                account: caller,
+                lesson_id,
+                block,
+            });
+
             Ok(())
         }
 
-        /// Returns the remaining blocks in the era.
+        /// Reveals the answer committed with `commit_answer` and claims the badge of
+        /// the lesson if it's right.
+        ///
+        /// The reveal must come after the commit block and within the reveal
+        /// window, and the era must be over. A wrong answer returns `false` and
+        /// locks the lesson for the caller during the answer lockout.
         #[ink(message)]
-        pub fn get_remaining(&self) -> u8 {
-            let current_block = self.env().block_number();
+        #[openbrush::modifiers(when_not_paused)]
+        pub fn reveal_answer(
+            &mut self,
+            lesson_id: LessonId,
+            answer: String,
+            salt: Hash,
+        ) -> Result<bool, Error> {
             let caller = self.env().caller();
+            let profile = self.user.get(caller).ok_or(Error::UserNotFound)?;
+            self.ensure_can_complete(&profile, lesson_id)?;
+            let quiz = self.quizzes.get(lesson_id).ok_or(Error::NoQuiz)?;
 
-            self.user.get(caller).map_or(0, |profile| {
-                if current_block - profile.start_block >= profile.claim_era as u32 {
-                    return 0
-                }
+            let current_block = self.env().block_number();
+            let commitment = self
+                .commitments
+                .get((caller, lesson_id))
+                .ok_or(Error::CommitmentNotFound)?;
+            ensure!(current_block > commitment.block, Error::RevealTooEarly);
+            ensure!(
+                current_block - commitment.block <= self.reveal_window,
+                Error::CommitmentExpired
+            );
+            ensure!(
+                commitment_hash(&answer, &salt, &caller) == commitment.hash,
+                Error::CommitmentMismatch
+            );
+            ensure!(
+                profile.remaining_at(current_block) == 0,
+                Error::TooEarlyToClaim
+            );
 
-                profile.claim_era - (current_block - profile.start_block) as u8
-            })
+            self.commitments.remove((caller, lesson_id));
+
+            // wrong answers are kept, so they can't fail the call
+            let correct = answer_hash(&quiz.salt, &answer) == quiz.answer_hash;
+            let locked_until = if correct {
+                None
+            } else {
+                let block = current_block.saturating_add(self.answer_lockout);
+                self.locked_until.insert((caller, lesson_id), &block);
+                Some(block)
+            };
+
+            self.env().emit_event(AnswerSubmitted {
+                account: caller,
+                lesson_id,
+                correct,
+                locked_until,
+            });
+
+            if !correct {
+                return Ok(false)
+            }
+
+            self.complete_lesson(caller, profile, lesson_id)?;
+            Ok(true)
         }
 
-        /// Returns the remaining blocks in the era for the given account.
+        /// Mint Wizard NFT
+        ///
+        /// The caller must have completed every active lesson.
         #[ink(message)]
-        pub fn get_remaining_for(&self, account: AccountId) -> u8 {
-            let current_block = self.env().block_number();
+        #[openbrush::modifiers(when_not_paused)]
+        pub fn mint_wizard(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            ensure!(!self.has_minted(caller), Error::AlreadyMinted);
+            ensure!(!self.lesson_order.is_empty(), Error::NoLessons);
+            ensure!(
+                self.has_completed_all_lessons(caller),
+                Error::NotAllBadgesCollected
+            );
 
-            self.user.get(account).map_or(0, |profile| {
-                if current_block - profile.start_block >= profile.claim_era as u32 {
-                    return 0
-                }
+            let token_id = self.wizard_client().mint_next(caller)?;
+            self.minted.insert(caller, &token_id);
 
-                profile.claim_era - (current_block - profile.start_block) as u8
-            })
+            self.env().emit_event(WizardMinted {
+                account: caller,
+                token_id,
+            });
+
+            Ok(())
         }
 
-        /// Returns the profile of the given account.
+        /// Returns the id of the Wizard NFT minted by the given account.
         #[ink(message)]
-        pub fn get_account_profile(&self, account: AccountId) -> Option<Profile> {
-            self.user.get(account)
+        pub fn minted_token_of(&self, account: AccountId) -> Option<Id> {
+            self.minted.get(account)
         }
 
-        /// Returns the profile of the caller.
+        /// Returns true if the given account has already minted the Wizard NFT.
         #[ink(message)]
-        pub fn get_profile(&self) -> Option<Profile> {
-            let caller = self.env().caller();
-            self.user.get(caller)
+        pub fn has_minted(&self, account: AccountId) -> bool {
+            self.minted.contains(account)
         }
 
-        /// Returns the badge of the caller.
+        /// Returns the number of badges required to mint the Wizard NFT, one per
+        /// active lesson.
         #[ink(message)]
-        pub fn get_badges(&self) -> u8 {
-            self.get_profile()
-                .map_or(0, |profile| profile.badges_claimed)
+        pub fn get_badges_required(&self) -> u8 {
+            self.lesson_order.len() as u8
         }
 
-        /// Returns the badge count of the given account.
+        /// Adds a lesson at the end of the active lessons and returns its id.
         #[ink(message)]
-        pub fn get_badges_for(&self, account: AccountId) -> u8 {
-            self.get_account_profile(account)
-                .map_or(0, |profile| profile.badges_claimed)
-        }
-    }
+        #[openbrush::modifiers(only_owner)]
+        pub fn add_lesson(
+            &mut self,
+            content_hash: Hash,
+            title: String,
+        ) -> Result<LessonId, Error> {
+            let id = self.insert_lesson(content_hash, title)?;
 
-    // cargo test --features e2e-tests -- --nocapture
-    #[cfg(all(test, feature = "e2e-tests"))]
-    mod e2e_tests {
+            self.env().emit_event(LessonAdded { id, content_hash });
 
-        use super::*;
-        use crate::address_of;
-        use wizard::WizardRef;
+            Ok(id)
+        }
 
-        use ink_e2e::{
-            build_message,
-            PolkadotConfig,
-        };
+        /// Removes a lesson from the active lessons, it stays queryable.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn retire_lesson(&mut self, id: LessonId) -> Result<(), Error> {
+            let position = self
+                .lesson_order
+                .iter()
+                .position(|lesson_id| *lesson_id == id)
+                .ok_or(Error::LessonNotFound)?;
+            let mut lesson = self.lessons.get(id).ok_or(Error::LessonNotFound)?;
 
-        use openbrush::contracts::ownable::ownable_external::Ownable;
+            self.lesson_order.remove(position);
+            lesson.retired = true;
+            self.lessons.insert(id, &lesson);
 
-        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+            self.env().emit_event(LessonRetired { id });
 
-        #[ink_e2e::test]
-        async fn check_default_owner_of_the_wizard_contract(
+            Ok(())
+        }
+
+        /// Sets the order of the active lessons.
+        ///
+        /// `order` must hold every active lesson exactly once.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn reorder_lessons(&mut self, order: Vec<LessonId>) -> Result<(), Error> {
+            let mut sorted = order.clone();
+            sorted.sort_unstable();
+            let mut active = self.lesson_order.clone();
+            active.sort_unstable();
+            ensure!(sorted == active, Error::InvalidLessonOrder);
+
+            self.lesson_order = order.clone();
+
+            self.env().emit_event(LessonsReordered { order });
+
+            Ok(())
+        }
+
+        /// Returns the lesson with the given id, retired or not.
+        #[ink(message)]
+        pub fn get_lesson(&self, id: LessonId) -> Option<Lesson> {
+            self.lessons.get(id)
+        }
+
+        /// Returns the active lessons in order.
+        #[ink(message)]
+        pub fn get_lessons(&self) -> Vec<Lesson> {
+            self.lesson_order
+                .iter()
+                .filter_map(|id| self.lessons.get(id))
+                .collect()
+        }
+
+        /// Gates the badge of an active lesson behind a quiz, replacing any
+        /// previous one.
+        ///
+        /// `answer_hash` is `answer_hash(salt, answer)` of the right answer.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_quiz(
+            &mut self,
+            lesson_id: LessonId,
+            salt: Hash,
+            answer_hash: Hash,
+        ) -> Result<(), Error> {
+            ensure!(
+                self.lesson_order.contains(&lesson_id),
+                Error::LessonNotFound
+            );
+
+            self.quizzes.insert(lesson_id, &Quiz { salt, answer_hash });
+
+            self.env().emit_event(QuizSet { lesson_id });
+
+            Ok(())
+        }
+
+        /// Returns the quiz of the given lesson.
+        #[ink(message)]
+        pub fn get_quiz(&self, lesson_id: LessonId) -> Option<Quiz> {
+            self.quizzes.get(lesson_id)
+        }
+
+        /// Sets the number of blocks a wrong answer locks a lesson for.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_answer_lockout(&mut self, blocks: BlockNumber) -> Result<(), Error> {
+            self.answer_lockout = blocks;
+            Ok(())
+        }
+
+        /// Returns the number of blocks a wrong answer locks a lesson for.
+        #[ink(message)]
+        pub fn get_answer_lockout(&self) -> BlockNumber {
+            self.answer_lockout
+        }
+
+        /// Sets the number of blocks after the commit block an answer can be
+        /// revealed in.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_reveal_window(&mut self, blocks: BlockNumber) -> Result<(), Error> {
+            ensure!(blocks > 0, Error::InvalidRevealWindow);
+
+            self.reveal_window = blocks;
+            Ok(())
+        }
+
+        /// Returns the number of blocks after the commit block an answer can be
+        /// revealed in.
+        #[ink(message)]
+        pub fn get_reveal_window(&self) -> BlockNumber {
+            self.reveal_window
+        }
+
+        /// Returns the commitment of the given account to the quiz of the lesson,
+        /// none if there is none or it expired.
+        #[ink(message)]
+        pub fn get_commitment(
+            &self,
+            account: AccountId,
+            lesson_id: LessonId,
+        ) -> Option<Commitment> {
+            let current_block = self.env().block_number();
+
+            self.commitments
+                .get((account, lesson_id))
+                .filter(|commitment| {
+                    current_block.saturating_sub(commitment.block) <= self.reveal_window
+                })
+        }
+
+        /// Returns the block from which the given account can answer the quiz of
+        /// the lesson again, none if it isn't locked out.
+        #[ink(message)]
+        pub fn get_locked_until(
+            &self,
+            account: AccountId,
+            lesson_id: LessonId,
+        ) -> Option<BlockNumber> {
+            let current_block = self.env().block_number();
+
+            self.locked_until
+                .get((account, lesson_id))
+                .filter(|block| *block > current_block)
+        }
+
+        /// Sets the number of blocks between two claims.
+        ///
+        /// Applies to the runs started afterwards, running ones keep their era.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_era_length(&mut self, era_length: BlockNumber) -> Result<(), Error> {
+            ensure!(era_length > 0, Error::InvalidEraLength);
+
+            self.era_length = era_length;
+            Ok(())
+        }
+
+        /// Returns the number of blocks between two claims.
+        #[ink(message)]
+        pub fn get_era_length(&self) -> BlockNumber {
+            self.era_length
+        }
+
+        /// Points Magink to another wizard contract.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_wizard_contract(
+            &mut self,
+            account_id: AccountId,
+        ) -> Result<(), Error> {
+            self.wizard =
+                <WizardRef as FromAccountId<Environment>>::from_account_id(account_id);
+            Ok(())
+        }
+
+        /// Sets the gas limit of the calls to the wizard contract.
+        ///
+        /// Zero forwards all the remaining gas.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_wizard_gas_limit(&mut self, gas_limit: u64) -> Result<(), Error> {
+            self.wizard_gas_limit = gas_limit;
+            Ok(())
+        }
+
+        /// Returns the gas limit of the calls to the wizard contract.
+        #[ink(message)]
+        pub fn get_wizard_gas_limit(&self) -> u64 {
+            self.wizard_gas_limit
+        }
+
+        /// Pauses `start`, `restart`, the claims, the answers and `mint_wizard`.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn pause(&mut self) -> Result<(), Error> {
+            Ok(pausable::Internal::_pause(self)?)
+        }
+
+        /// Lifts a pause set with `pause`.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn unpause(&mut self) -> Result<(), Error> {
+            Ok(pausable::Internal::_unpause(self)?)
+        }
+
+        /// Replaces the code of this contract, keeping its storage.
+        ///
+        /// Call `migrate` afterwards if the new code bumps the storage version.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)?;
+
+            self.env().emit_event(Upgraded { code_hash });
+
+            Ok(())
+        }
+
+        /// Brings the stored data up to `STORAGE_VERSION` after an upgrade.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn migrate(&mut self) -> Result<(), Error> {
+            let from_version = self.get_version();
+            ensure!(from_version < STORAGE_VERSION, Error::AlreadyMigrated);
+
+            // migration steps go here, one per version
+            self.version.set(&STORAGE_VERSION);
+
+            self.env().emit_event(Migrated {
+                from_version,
+                to_version: STORAGE_VERSION,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the version of the stored data.
+        #[ink(message)]
+        pub fn get_version(&self) -> u32 {
+            self.version.get().unwrap_or_default()
+        }
+
+        /// Returns the account id of the wizard contract.
+        #[ink(message)]
+        pub fn get_wizard_contract(&self) -> AccountId {
+            self.wizard.to_account_id()
+        }
+
+        /// Points Magink to the badge contract, which then mints a soulbound token
+        /// for every completed lesson. Magink needs its `MINTER` role.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_badge_contract(&mut self, account_id: AccountId) -> Result<(), Error> {
+            self.badge = Some(<BadgeRef as FromAccountId<Environment>>::from_account_id(
+                account_id,
+            ));
+            Ok(())
+        }
+
+        /// Returns the account id of the badge contract, none if no badge tokens
+        /// are minted.
+        #[ink(message)]
+        pub fn get_badge_contract(&self) -> Option<AccountId> {
+            self.badge.as_ref().map(|badge| badge.to_account_id())
+        }
+
+        /// Sets the gas limit of the calls to the badge contract.
+        ///
+        /// Zero forwards all the remaining gas.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_badge_gas_limit(&mut self, gas_limit: u64) -> Result<(), Error> {
+            self.badge_gas_limit = gas_limit;
+            Ok(())
+        }
+
+        /// Returns the gas limit of the calls to the badge contract.
+        #[ink(message)]
+        pub fn get_badge_gas_limit(&self) -> u64 {
+            self.badge_gas_limit
+        }
+
+        /// Returns the id of the badge token minted to the given account for the
+        /// lesson.
+        #[ink(message)]
+        pub fn badge_token_of(
+            &self,
+            account: AccountId,
+            lesson_id: LessonId,
+        ) -> Option<Id> {
+            self.badge_tokens.get((account, lesson_id))
+        }
+
+        /// Returns the remaining blocks in the era.
+        #[ink(message)]
+        pub fn get_remaining(&self) -> BlockNumber {
+            self.get_remaining_for(self.env().caller())
+        }
+
+        /// Returns the remaining blocks in the era for the given account.
+        #[ink(message)]
+        pub fn get_remaining_for(&self, account: AccountId) -> BlockNumber {
+            let current_block = self.env().block_number();
+
+            self.user
+                .get(account)
+                .map_or(0, |profile| profile.remaining_at(current_block))
+        }
+
+        /// Returns the profile of the given account.
+        #[ink(message)]
+        pub fn get_account_profile(&self, account: AccountId) -> Option<Profile> {
+            self.user.get(account)
+        }
+
+        /// Returns the profile of the caller.
+        #[ink(message)]
+        pub fn get_profile(&self) -> Option<Profile> {
+            let caller = self.env().caller();
+            self.user.get(caller)
+        }
+
+        /// Returns the badge of the caller.
+        #[ink(message)]
+        pub fn get_badges(&self) -> u8 {
+            self.get_badges_for(self.env().caller())
+        }
+
+        /// Returns the badge count of the given account, one per completed lesson.
+        #[ink(message)]
+        pub fn get_badges_for(&self, account: AccountId) -> u8 {
+            self.get_account_profile(account)
+                .map_or(0, |profile| profile.badges_claimed())
+        }
+
+        /// Returns the ids of the lessons completed by the given account, in
+        /// ascending order.
+        #[ink(message)]
+        pub fn get_completed_lessons(&self, account: AccountId) -> Vec<LessonId> {
+            self.get_account_profile(account)
+                .map_or(Vec::new(), |profile| profile.completed_lessons())
+        }
+
+        /// Returns the profiles of the given accounts, in order.
+        #[ink(message)]
+        pub fn get_profiles(
+            &self,
+            accounts: Vec<AccountId>,
+        ) -> Result<Vec<Option<Profile>>, Error> {
+            ensure_batch_size(&accounts)?;
+
+            Ok(accounts
+                .into_iter()
+                .map(|account| self.get_account_profile(account))
+                .collect())
+        }
+
+        /// Returns the badge counts of the given accounts, in order.
+        #[ink(message)]
+        pub fn get_badges_many(
+            &self,
+            accounts: Vec<AccountId>,
+        ) -> Result<Vec<u8>, Error> {
+            ensure_batch_size(&accounts)?;
+
+            Ok(accounts
+                .into_iter()
+                .map(|account| self.get_badges_for(account))
+                .collect())
+        }
+
+        /// Returns everything known about the given account in one call.
+        #[ink(message)]
+        pub fn status(&self, account: AccountId) -> Status {
+            let profile = self.get_account_profile(account);
+            let minted_token_id = self.minted_token_of(account);
+
+            let client = self.wizard_client();
+            let remaining_supply = match (client.max_supply(), client.total_supply()) {
+                (Ok(max_supply), Ok(total_supply)) => {
+                    Some(max_supply.saturating_sub(total_supply as u64))
+                }
+                _ => None,
+            };
+
+            let can_mint = profile.is_some()
+                && minted_token_id.is_none()
+                && !self.lesson_order.is_empty()
+                && self.has_completed_all_lessons(account)
+                && !Pausable::paused(self)
+                && remaining_supply != Some(0);
+
+            Status {
+                next_claim_block: profile.as_ref().map(Profile::next_claim_block),
+                profile,
+                remaining_blocks: self.get_remaining_for(account),
+                badges_required: self.get_badges_required(),
+                can_mint,
+                minted_token_id,
+                remaining_supply,
+            }
+        }
+
+        fn ensure_can_complete(
+            &self,
+            profile: &Profile,
+            lesson_id: LessonId,
+        ) -> Result<(), Error> {
+            ensure!(
+                self.lesson_order.contains(&lesson_id),
+                Error::LessonNotFound
+            );
+            ensure!(
+                !profile.has_completed(lesson_id),
+                Error::LessonAlreadyCompleted
+            );
+            Ok(())
+        }
+
+        fn complete_lesson(
+            &mut self,
+            caller: AccountId,
+            mut profile: Profile,
+            lesson_id: LessonId,
+        ) -> Result<(), Error> {
+            let current_block = self.env().block_number();
+            ensure!(
+                profile.remaining_at(current_block) == 0,
+                Error::TooEarlyToClaim
+            );
+
+            // mint first so a failed call leaves no state behind, a lesson completed
+            // again after a restart keeps its first badge
+            let minted = match &self.badge {
+                Some(badge) if !self.badge_tokens.contains((caller, lesson_id)) => {
+                    Some(
+                        BadgeClient::connect(badge, self.badge_gas_limit)
+                            .mint_badge(caller, lesson_id)?,
+                    )
+                }
+                _ => None,
+            };
+
+            // update profile
+            profile.completed_lessons |= 1 << lesson_id;
+            profile.start_block = current_block;
+
+            self.user.insert(caller, &profile);
+
+            self.env().emit_event(BadgeClaimed {
+                account: caller,
+                lesson_id,
+                badges_claimed: profile.badges_claimed(),
+                block: profile.start_block,
+            });
+
+            if let Some(token_id) = minted {
+                self.badge_tokens.insert((caller, lesson_id), &token_id);
+
+                self.env().emit_event(BadgeMinted {
+                    account: caller,
+                    lesson_id,
+                    token_id,
+                });
+            }
+
+            Ok(())
+        }
+
+        fn has_completed_all_lessons(&self, account: AccountId) -> bool {
+            self.get_account_profile(account).map_or(false, |profile| {
+                self.lesson_order
+                    .iter()
+                    .all(|id| profile.has_completed(*id))
+            })
+        }
+
+        fn wizard_client(&self) -> WizardClient {
+            WizardClient::connect(&self.wizard, self.wizard_gas_limit)
+        }
+
+        fn insert_lesson(
+            &mut self,
+            content_hash: Hash,
+            title: String,
+        ) -> Result<LessonId, Error> {
+            let id = self.next_lesson_id;
+            ensure!(id < MAX_LESSONS, Error::TooManyLessons);
+
+            let lesson = Lesson {
+                id,
+                content_hash,
+                title,
+                retired: false,
+            };
+
+            self.lessons.insert(id, &lesson);
+            self.lesson_order.push(id);
+            self.next_lesson_id = id + 1;
+
+            Ok(id)
+        }
+    }
+
+    /// In-memory wizard contracts for the off-chain unit tests.
+    #[cfg(test)]
+    pub mod mock {
+        use super::*;
+
+        use std::{
+            cell::RefCell,
+            collections::BTreeMap,
+        };
+
+        #[derive(Default)]
+        struct Collection {
+            max_supply: u64,
+            gas_required: u64,
+            owners: Vec<(Id, AccountId)>,
+        }
+
+        #[derive(Default)]
+        struct BadgeCollection {
+            gas_required: u64,
+            minter_revoked: bool,
+            // owner and lesson of every badge, the token id is the position + 1
+            owners: Vec<(AccountId, LessonId)>,
+        }
+
+        thread_local! {
+            static COLLECTIONS: RefCell<BTreeMap<AccountId, Collection>> =
+                RefCell::new(BTreeMap::new());
+
+            static BADGES: RefCell<BTreeMap<AccountId, BadgeCollection>> =
+                RefCell::new(BTreeMap::new());
+        }
+
+        /// Deploys an empty wizard collection at `address`.
+        pub fn deploy(address: AccountId, max_supply: u64) {
+            COLLECTIONS.with(|collections| {
+                collections.borrow_mut().insert(
+                    address,
+                    Collection {
+                        max_supply,
+                        ..Default::default()
+                    },
+                );
+            });
+        }
+
+        /// Makes the calls to the wizard at `address` trap with less than `gas`.
+        pub fn set_gas_required(address: AccountId, gas: u64) {
+            COLLECTIONS.with(|collections| {
+                if let Some(collection) = collections.borrow_mut().get_mut(&address) {
+                    collection.gas_required = gas;
+                }
+            });
+        }
+
+        /// Mints the token `id` without Magink, as another minter could do.
+        pub fn mint_directly(address: AccountId, to: AccountId, id: Id) {
+            COLLECTIONS.with(|collections| {
+                if let Some(collection) = collections.borrow_mut().get_mut(&address) {
+                    collection.owners.push((id, to));
+                }
+            });
+        }
+
+        /// Returns the owner of the token `id` of the wizard at `address`.
+        pub fn owner_of(address: AccountId, id: Id) -> Option<AccountId> {
+            COLLECTIONS.with(|collections| {
+                collections.borrow().get(&address).and_then(|collection| {
+                    collection
+                        .owners
+                        .iter()
+                        .find(|(token, _)| *token == id)
+                        .map(|(_, owner)| *owner)
+                })
+            })
+        }
+
+        /// Deploys an empty badge collection at `address`, Magink is a minter.
+        pub fn deploy_badge(address: AccountId) {
+            BADGES.with(|badges| {
+                badges
+                    .borrow_mut()
+                    .insert(address, BadgeCollection::default());
+            });
+        }
+
+        /// Makes the calls to the badge contract at `address` trap with less than
+        /// `gas`.
+        pub fn set_badge_gas_required(address: AccountId, gas: u64) {
+            BADGES.with(|badges| {
+                if let Some(collection) = badges.borrow_mut().get_mut(&address) {
+                    collection.gas_required = gas;
+                }
+            });
+        }
+
+        /// Revokes the `MINTER` role of Magink on the badge contract at `address`.
+        pub fn revoke_badge_minter(address: AccountId) {
+            BADGES.with(|badges| {
+                if let Some(collection) = badges.borrow_mut().get_mut(&address) {
+                    collection.minter_revoked = true;
+                }
+            });
+        }
+
+        /// Returns the owner and the lesson of the badge `id` at `address`.
+        pub fn badge_of(address: AccountId, id: Id) -> Option<(AccountId, LessonId)> {
+            let Id::U64(id) = id else { return None };
+
+            BADGES.with(|badges| {
+                badges.borrow().get(&address).and_then(|collection| {
+                    collection
+                        .owners
+                        .get((id as usize).checked_sub(1)?)
+                        .copied()
+                })
+            })
+        }
+
+        /// Calls an in-memory badge collection as if it was deployed on chain.
+        pub struct MockBadge {
+            address: AccountId,
+            gas_limit: u64,
+        }
+
+        impl BadgeBackend for MockBadge {
+            fn connect(badge: &BadgeRef, gas_limit: u64) -> Self {
+                Self {
+                    address: badge.to_account_id(),
+                    gas_limit,
+                }
+            }
+
+            fn mint_badge(
+                &mut self,
+                to: AccountId,
+                lesson_id: LessonId,
+            ) -> Result<Id, Error> {
+                let result = BADGES.with(|badges| {
+                    match badges.borrow_mut().get_mut(&self.address) {
+                        None => Err(ink::env::Error::NotCallable),
+                        Some(collection)
+                            if self.gas_limit != 0
+                                && self.gas_limit < collection.gas_required =>
+                        {
+                            Err(ink::env::Error::CalleeTrapped)
+                        }
+                        Some(collection) if collection.minter_revoked => {
+                            Ok(Ok(Err(PSP34Error::Custom(String::from(
+                                "AC::MissingRole",
+                            )))))
+                        }
+                        Some(collection) => {
+                            collection.owners.push((to, lesson_id));
+                            Ok(Ok(Ok(Id::U64(collection.owners.len() as u64))))
+                        }
+                    }
+                });
+
+                badge_call_result(result)?.map_err(Error::Badge)
+            }
+        }
+
+        /// Calls an in-memory wizard as if it was deployed on chain.
+        pub struct MockWizard {
+            address: AccountId,
+            gas_limit: u64,
+        }
+
+        impl MockWizard {
+            fn call<R>(&self, f: impl FnOnce(&mut Collection) -> R) -> Result<R, Error> {
+                let result = COLLECTIONS.with(|collections| {
+                    match collections.borrow_mut().get_mut(&self.address) {
+                        None => Err(ink::env::Error::NotCallable),
+                        Some(collection)
+                            if self.gas_limit != 0
+                                && self.gas_limit < collection.gas_required =>
+                        {
+                            Err(ink::env::Error::CalleeTrapped)
+                        }
+                        Some(collection) => Ok(Ok(f(collection))),
+                    }
+                });
+
+                wizard_call_result(result)
+            }
+        }
+
+        impl WizardBackend for MockWizard {
+            fn connect(wizard: &WizardRef, gas_limit: u64) -> Self {
+                Self {
+                    address: wizard.to_account_id(),
+                    gas_limit,
+                }
+            }
+
+            fn total_supply(&self) -> Result<Balance, Error> {
+                self.call(|collection| collection.owners.len() as Balance)
+            }
+
+            fn max_supply(&self) -> Result<u64, Error> {
+                self.call(|collection| collection.max_supply)
+            }
+
+            fn mint_next(&mut self, to: AccountId) -> Result<Id, Error> {
+                self.call(|collection| {
+                    if collection.owners.len() as u64 >= collection.max_supply {
+                        return Err(PSP34Error::Custom(String::from("CollectionFull")))
+                    }
+
+                    let is_taken = |id: u64| {
+                        collection
+                            .owners
+                            .iter()
+                            .any(|(token, _)| *token == Id::U64(id))
+                    };
+
+                    let mut next = collection.owners.len() as u64 + 1;
+                    while is_taken(next) {
+                        next += 1;
+                    }
+
+                    collection.owners.push((Id::U64(next), to));
+                    Ok(Id::U64(next))
+                })?
+                .map_err(Error::from)
+            }
+        }
+    }
+
+    // cargo test --features e2e-tests -- --nocapture
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+
+        use super::*;
+        use crate::address_of;
+        use wizard::{
+            WizardRef,
+            MINTER,
+        };
+
+        use ink_e2e::{
+            build_message,
+            PolkadotConfig,
+        };
+
+        use badge::{
+            BadgeRef,
+            MINTER as BADGE_MINTER,
+        };
+
+        use openbrush::contracts::{
+            access_control::{
+                access_control_external::AccessControl,
+                DEFAULT_ADMIN_ROLE,
+            },
+            psp34::psp34_external::PSP34,
+        };
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        type Event = <Magink as ink::reflect::ContractEventBase>::Type;
+
+        /// Decodes the events emitted by `contract` during the call.
+        fn contract_events<V>(
+            result: &ink_e2e::CallResult<PolkadotConfig, Environment, V>,
+            contract: &AccountId,
+        ) -> Vec<Event> {
+            result
+                .events
+                .iter()
+                .filter_map(|event| {
+                    let event = event.expect("reading event failed");
+                    if event.pallet_name() != "Contracts"
+                        || event.variant_name() != "ContractEmitted"
+                    {
+                        return None
+                    }
+
+                    let (emitter, data) =
+                        <(AccountId, Vec<u8>) as scale::Decode>::decode(
+                            &mut event.field_bytes(),
+                        )
+                        .expect("invalid ContractEmitted event");
+
+                    (&emitter == contract).then(|| {
+                        <Event as scale::Decode>::decode(&mut &data[..])
+                            .expect("invalid magink event")
+                    })
+                })
+                .collect()
+        }
+
+        #[ink_e2e::test]
+        async fn check_default_minter_of_the_wizard_contract(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let constructor = WizardRef::new(10);
+
+            let account_id = client
+                .instantiate("wizard", &ink_e2e::bob(), constructor, 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+
+            let is_minter = {
+                let msg = build_message::<WizardRef>(account_id.clone())
+                    .call(|contract| contract.has_role(MINTER, Some(address_of!(bob))));
+                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert!(is_minter);
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_mint_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let max_supply: u64 = 10;
+
+            // instantiate wizard contract
+            let wizard_constructor = WizardRef::new(max_supply);
+
+            let wizard_account_id = client
+                .instantiate("wizard", &ink_e2e::alice(), wizard_constructor, 0, None)
+                .await
+                .expect("wizard contract instantiate failed")
+                .account_id;
+
+            // instantiate magink contract
+            let magink_constructor = MaginkRef::new(wizard_account_id);
+
+            let magink_account_id = client
+                .instantiate("magink", &ink_e2e::alice(), magink_constructor, 0, None)
+                .await
+                .expect("magink contract instantiate failed")
+                .account_id;
+
+            // allow magink to mint
+            let grant_minter = build_message::<WizardRef>(wizard_account_id.clone())
+                .call(|p| p.grant_role(MINTER, Some(magink_account_id)));
+
+            client
+                .call(&ink_e2e::alice(), grant_minter, 0, None)
+                .await
+                .expect("calling grant_role failed");
+
+            // verfy it
+            let is_minter = build_message::<WizardRef>(wizard_account_id.clone())
+                .call(|p| p.has_role(MINTER, Some(magink_account_id)));
+
+            let is_minter_result = client
+                .call_dry_run(&ink_e2e::alice(), &is_minter, 0, None)
+                .await
+                .return_value();
+
+            assert!(is_minter_result);
+
+            // check total supply
+            let total_supply = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|contract| contract.total_supply());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(total_supply, Ok(0));
+
+            // register a lesson to claim
+            let add_lesson_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| {
+                    magink.add_lesson(Hash::from([0x01; 32]), String::from("ink!"))
+                });
+
+            client
+                .call(&ink_e2e::alice(), add_lesson_msg, 0, None)
+                .await
+                .expect("calling add_lesson failed");
+
+            // start
+            let era_length_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.set_era_length(1));
+
+            client
+                .call(&ink_e2e::alice(), era_length_msg, 0, None)
+                .await
+                .expect("calling set_era_length failed");
+
+            let start_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.start());
+
+            let start_result = client
+                .call(&ink_e2e::alice(), start_msg, 0, None)
+                .await
+                .expect("calling start failed");
+
+            let events = contract_events(&start_result, &magink_account_id);
+            assert_eq!(events.len(), 1);
+            match &events[0] {
+                Event::Started(event) => {
+                    assert_eq!(event.account, address_of!(alice));
+                    assert_eq!(event.era, 1);
+                }
+                _ => panic!("expected Started event"),
+            }
+
+            // claim
+            let claim_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.claim());
+
+            let claim_result = client
+                .call(&ink_e2e::alice(), claim_msg, 0, None)
+                .await
+                .expect("calling claim failed");
+
+            let events = contract_events(&claim_result, &magink_account_id);
+            assert_eq!(events.len(), 1);
+            match &events[0] {
+                Event::BadgeClaimed(event) => {
+                    assert_eq!(event.account, address_of!(alice));
+                    assert_eq!(event.lesson_id, 0);
+                    assert_eq!(event.badges_claimed, 1);
+                }
+                _ => panic!("expected BadgeClaimed event"),
+            }
+
+            let badges = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.get_badges());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(badges, 1);
+
+            // mint new token
+            let mint_wizard_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.mint_wizard());
+
+            let mint_result = client
+                .call(&ink_e2e::alice(), mint_wizard_msg, 0, None)
+                .await
+                .expect("minting new token failed");
+
+            let events = contract_events(&mint_result, &magink_account_id);
+            assert_eq!(events.len(), 1);
+            match &events[0] {
+                Event::WizardMinted(event) => {
+                    assert_eq!(event.account, address_of!(alice));
+                    assert_eq!(event.token_id, Id::U64(1));
+                }
+                _ => panic!("expected WizardMinted event"),
+            }
+
+            let total_supply = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|contract| contract.total_supply());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(total_supply, Ok(1));
+
+            // minted token is recorded for the caller
+            let minted_token = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.minted_token_of(address_of!(alice)));
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(minted_token, Some(Id::U64(1)));
+
+            // second mint from the same account fails
+            let mint_again = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.mint_wizard());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(mint_again, Err(Error::AlreadyMinted));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "fixtures/upgrade/Cargo.toml")]
+        async fn e2e_upgrade_keeps_profiles(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let magink_constructor = MaginkRef::new(address_of!(bob));
+
+            let magink_account_id = client
+                .instantiate("magink", &ink_e2e::alice(), magink_constructor, 0, None)
+                .await
+                .expect("magink contract instantiate failed")
+                .account_id;
+
+            let era_length_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.set_era_length(1));
+
+            client
+                .call(&ink_e2e::alice(), era_length_msg, 0, None)
+                .await
+                .expect("calling set_era_length failed");
+
+            // register a lesson to claim
+            let add_lesson_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| {
+                    magink.add_lesson(Hash::from([0x01; 32]), String::from("ink!"))
+                });
+
+            client
+                .call(&ink_e2e::alice(), add_lesson_msg, 0, None)
+                .await
+                .expect("calling add_lesson failed");
+
+            let start_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.start());
+
+            client
+                .call(&ink_e2e::alice(), start_msg, 0, None)
+                .await
+                .expect("calling start failed");
+
+            let claim_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.claim());
+
+            client
+                .call(&ink_e2e::alice(), claim_msg, 0, None)
+                .await
+                .expect("calling claim failed");
+
+            let profile_before = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.get_profile());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert!(profile_before.is_some());
+
+            // stand-in for the next release, its `migrate` bumps the storage version
+            let code_hash = client
+                .upload("magink_upgrade_fixture", &ink_e2e::alice(), None)
+                .await
+                .expect("uploading magink_upgrade_fixture failed")
+                .code_hash;
+
+            // only the owner can upgrade
+            let upgrade = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.upgrade(code_hash));
+
+                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(upgrade, Err(Error::Ownable(OwnableError::CallerIsNotOwner)));
+
+            let upgrade_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.upgrade(code_hash));
+
+            let upgrade_result = client
+                .call(&ink_e2e::alice(), upgrade_msg, 0, None)
+                .await
+                .expect("calling upgrade failed");
+
+            let events = contract_events(&upgrade_result, &magink_account_id);
+            assert_eq!(events.len(), 1);
+            match &events[0] {
+                Event::Upgraded(event) => assert_eq!(event.code_hash, code_hash),
+                _ => panic!("expected Upgraded event"),
+            }
+
+            // the stored data stays at the old version until migrated
+            let version = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.get_version());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(version, STORAGE_VERSION);
+
+            let migrate_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.migrate());
+
+            client
+                .call(&ink_e2e::alice(), migrate_msg, 0, None)
+                .await
+                .expect("calling migrate failed");
+
+            let version = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.get_version());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(version, STORAGE_VERSION + 1);
+
+            // back to the magink code to read the profile
+            let magink_code_hash = client
+                .upload("magink", &ink_e2e::alice(), None)
+                .await
+                .expect("uploading magink failed")
+                .code_hash;
+
+            let upgrade_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.upgrade(magink_code_hash));
+
+            client
+                .call(&ink_e2e::alice(), upgrade_msg, 0, None)
+                .await
+                .expect("calling upgrade failed");
+
+            // the profile survives both upgrades
+            let profile_after = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.get_profile());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(profile_after, profile_before);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_new_with_wizard_works(
             mut client: ink_e2e::Client<C, E>,
         ) -> E2EResult<()> {
-            let constructor = WizardRef::new(10);
+            let wizard_code_hash = client
+                .upload("wizard", &ink_e2e::alice(), None)
+                .await
+                .expect("uploading wizard failed")
+                .code_hash;
+
+            // instantiate magink together with its own wizard
+            let magink_constructor = MaginkRef::new_with_wizard(
+                wizard_code_hash,
+                10,
+                String::from("Sorcerer"),
+                String::from("SRC"),
+                String::from("ipfs://sorcerer/"),
+                address_of!(alice),
+            );
+
+            let magink_account_id = client
+                .instantiate("magink", &ink_e2e::alice(), magink_constructor, 0, None)
+                .await
+                .expect("magink contract instantiate failed")
+                .account_id;
+
+            let wizard_account_id = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.get_wizard_contract());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            // magink can mint on the wizard it instantiated
+            let is_minter = {
+                let msg = build_message::<WizardRef>(wizard_account_id.clone())
+                    .call(|wizard| wizard.has_role(MINTER, Some(magink_account_id)));
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert!(is_minter);
+
+            // but only the deployer administers it
+            let magink_is_admin = {
+                let msg = build_message::<WizardRef>(wizard_account_id.clone()).call(
+                    |wizard| wizard.has_role(DEFAULT_ADMIN_ROLE, Some(magink_account_id)),
+                );
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert!(!magink_is_admin);
+
+            let alice_is_admin = {
+                let msg = build_message::<WizardRef>(wizard_account_id.clone()).call(
+                    |wizard| {
+                        wizard.has_role(DEFAULT_ADMIN_ROLE, Some(address_of!(alice)))
+                    },
+                );
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert!(alice_is_admin);
+
+            let max_supply = {
+                let msg = build_message::<WizardRef>(wizard_account_id.clone())
+                    .call(|wizard| wizard.max_supply());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(max_supply, 10);
+
+            let add_lesson_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| {
+                    magink.add_lesson(Hash::from([0x01; 32]), String::from("ink!"))
+                });
+
+            client
+                .call(&ink_e2e::alice(), add_lesson_msg, 0, None)
+                .await
+                .expect("calling add_lesson failed");
+
+            // mint without any role grant
+            let era_length_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.set_era_length(1));
+
+            client
+                .call(&ink_e2e::alice(), era_length_msg, 0, None)
+                .await
+                .expect("calling set_era_length failed");
+
+            let start_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.start());
+
+            client
+                .call(&ink_e2e::alice(), start_msg, 0, None)
+                .await
+                .expect("calling start failed");
+
+            let claim_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.claim());
+
+            client
+                .call(&ink_e2e::alice(), claim_msg, 0, None)
+                .await
+                .expect("calling claim failed");
+
+            let mint_wizard_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.mint_wizard());
+
+            client
+                .call(&ink_e2e::alice(), mint_wizard_msg, 0, None)
+                .await
+                .expect("minting new token failed");
+
+            let minted_token = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.minted_token_of(address_of!(alice)));
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(minted_token, Some(Id::U64(1)));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_claim_mints_soulbound_badge(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let badge_account_id = client
+                .instantiate("badge", &ink_e2e::alice(), BadgeRef::new(), 0, None)
+                .await
+                .expect("badge contract instantiate failed")
+                .account_id;
+
+            let magink_constructor = MaginkRef::new(address_of!(bob));
+
+            let magink_account_id = client
+                .instantiate("magink", &ink_e2e::alice(), magink_constructor, 0, None)
+                .await
+                .expect("magink contract instantiate failed")
+                .account_id;
+
+            let set_badge_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.set_badge_contract(badge_account_id));
+
+            client
+                .call(&ink_e2e::alice(), set_badge_msg, 0, None)
+                .await
+                .expect("calling set_badge_contract failed");
+
+            let add_lesson_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| {
+                    magink.add_lesson(Hash::from([0x01; 32]), String::from("ink!"))
+                });
+
+            client
+                .call(&ink_e2e::alice(), add_lesson_msg, 0, None)
+                .await
+                .expect("calling add_lesson failed");
+
+            let era_length_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.set_era_length(1));
+
+            client
+                .call(&ink_e2e::alice(), era_length_msg, 0, None)
+                .await
+                .expect("calling set_era_length failed");
+
+            let start_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.start());
+
+            client
+                .call(&ink_e2e::alice(), start_msg, 0, None)
+                .await
+                .expect("calling start failed");
+
+            // the claim fails while magink can't mint badges
+            let claim = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.claim());
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(
+                claim,
+                Err(Error::Badge(PSP34Error::Custom(String::from(
+                    "AC::MissingRole"
+                ))))
+            );
+
+            // allow magink to mint badges
+            let grant_minter = build_message::<BadgeRef>(badge_account_id.clone())
+                .call(|badge| badge.grant_role(BADGE_MINTER, Some(magink_account_id)));
+
+            client
+                .call(&ink_e2e::alice(), grant_minter, 0, None)
+                .await
+                .expect("calling grant_role failed");
+
+            let claim_msg = build_message::<MaginkRef>(magink_account_id.clone())
+                .call(|magink| magink.claim());
+
+            let claim_result = client
+                .call(&ink_e2e::alice(), claim_msg, 0, None)
+                .await
+                .expect("calling claim failed");
+
+            let events = contract_events(&claim_result, &magink_account_id);
+            assert_eq!(events.len(), 2);
+            match &events[1] {
+                Event::BadgeMinted(event) => {
+                    assert_eq!(event.account, address_of!(alice));
+                    assert_eq!(event.lesson_id, 0);
+                    assert_eq!(event.token_id, Id::U64(1));
+                }
+                _ => panic!("expected BadgeMinted event"),
+            }
+
+            let badge_token = {
+                let msg = build_message::<MaginkRef>(magink_account_id.clone())
+                    .call(|magink| magink.badge_token_of(address_of!(alice), 0));
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(badge_token, Some(Id::U64(1)));
+
+            // the token carries the lesson and stays with the learner
+            let lesson = {
+                let msg = build_message::<BadgeRef>(badge_account_id.clone())
+                    .call(|badge| badge.lesson_of(1));
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(lesson, Some(0));
+
+            let transfer = {
+                let msg = build_message::<BadgeRef>(badge_account_id.clone())
+                    .call(|badge| badge.transfer(address_of!(bob), Id::U64(1), vec![]));
+
+                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(transfer, Err(PSP34Error::Custom(String::from("Soulbound"))));
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn start_works() {
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            println!("get {:?}", magink.get_remaining());
+
+            assert_eq!(Ok(()), magink.set_era_length(10));
+            assert_eq!(Ok(()), magink.start());
+            assert_eq!(10, magink.get_remaining());
+
+            advance_block();
+            assert_eq!(9, magink.get_remaining());
+        }
+
+        #[ink::test]
+        fn claim_works() {
+            const ERA: u32 = 10;
+            let accounts = default_accounts();
+
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+
+            assert_eq!(Ok(()), magink.set_era_length(ERA));
+            assert_eq!(Ok(()), magink.start());
+
+            advance_n_blocks(ERA - 1);
+            assert_eq!(1, magink.get_remaining());
+
+            // claim fails, too early
+            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+
+            // claim succeeds
+            advance_block();
+            assert_eq!(Ok(()), magink.claim());
+            assert_eq!(1, magink.get_badges());
+            assert_eq!(1, magink.get_badges_for(accounts.alice));
+            assert_eq!(1, magink.get_badges());
+            assert_eq!(10, magink.get_remaining());
+
+            // claim fails, too early
+            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+
+            advance_block();
+            assert_eq!(9, magink.get_remaining());
+
+            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+        }
+
+        #[ink::test]
+        fn start_and_claim_emit_events() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+
+            let events = recorded_events();
+            assert_eq!(2, events.len());
+
+            match &events[0] {
+                Event::Started(event) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.era, 1);
+                    assert_eq!(event.start_block, 0);
+                }
+                _ => panic!("expected Started event"),
+            }
+
+            match &events[1] {
+                Event::BadgeClaimed(event) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.lesson_id, 0);
+                    assert_eq!(event.badges_claimed, 1);
+                    assert_eq!(event.block, 1);
+                }
+                _ => panic!("expected BadgeClaimed event"),
+            }
+
+            // failed calls do not emit events
+            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+            assert_eq!(Err(Error::AlreadyStarted), magink.start());
+            assert_eq!(2, recorded_events().len());
+        }
+
+        #[ink::test]
+        fn mint_check_works() {
+            const ERA: u32 = 3;
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            mock::deploy(AccountId::from([0x01; 32]), 10);
+
+            assert_eq!(Ok(()), magink.set_era_length(ERA));
+            assert_eq!(Ok(()), magink.start());
+
+            assert_eq!(3, magink.get_remaining());
+            assert_eq!(0, magink.get_badges());
+
+            advance_block();
+            assert_eq!(2, magink.get_remaining());
+            advance_block();
+            assert_eq!(1, magink.get_remaining());
+            advance_block();
+            assert_eq!(0, magink.get_remaining());
+
+            // mint wizard returns error since not all badges are collected
+            assert!(magink.mint_wizard().is_err());
+            assert_eq!(magink.mint_wizard(), Err(Error::NotAllBadgesCollected));
+
+            assert_eq!(Ok(()), magink.claim());
+            assert_eq!(3, magink.get_remaining());
+            assert_eq!(1, magink.get_badges());
+
+            advance_block();
+            assert_eq!(2, magink.get_remaining());
+            advance_block();
+            assert_eq!(1, magink.get_remaining());
+            advance_block();
+            assert_eq!(0, magink.get_remaining());
+
+            assert_eq!(1, magink.get_badges());
+
+            assert_eq!(Ok(()), magink.mint_wizard());
+            assert_eq!(Ok(1), magink.total_supply());
+        }
+
+        #[ink::test]
+        fn mint_below_badges_required_fails() {
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(1, magink.get_badges());
+
+            assert_eq!(magink.mint_wizard(), Err(Error::NotAllBadgesCollected));
+        }
+
+        #[ink::test]
+        fn mint_at_badges_required_works() {
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+            mock::deploy(AccountId::from([0x01; 32]), 10);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 2);
+            assert_eq!(2, magink.get_badges());
+
+            assert_eq!(Ok(()), magink.mint_wizard());
+        }
+
+        #[ink::test]
+        fn mint_above_badges_required_works() {
+            let mut magink = init(AccountId::from([0x01; 32]), 3);
+            mock::deploy(AccountId::from([0x01; 32]), 10);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 3);
+            assert_eq!(3, magink.get_badges());
+
+            // retiring a lesson lowers the threshold below the claimed badges
+            assert_eq!(Ok(()), magink.retire_lesson(1));
+            assert_eq!(2, magink.get_badges_required());
+
+            assert_eq!(Ok(()), magink.mint_wizard());
+        }
+
+        #[ink::test]
+        fn second_mint_from_same_account_fails() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            mock::deploy(AccountId::from([0x01; 32]), 10);
+
+            assert!(!magink.has_minted(accounts.alice));
+            assert_eq!(magink.minted_token_of(accounts.alice), None);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Ok(()), magink.mint_wizard());
+
+            assert!(magink.has_minted(accounts.alice));
+            assert_eq!(magink.minted_token_of(accounts.alice), Some(Id::U64(1)));
+            assert_eq!(magink.mint_wizard(), Err(Error::AlreadyMinted));
+            assert_eq!(Ok(1), magink.total_supply());
+
+            assert!(!magink.has_minted(accounts.bob));
+        }
+
+        #[ink::test]
+        fn mint_wizard_works() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            let mut magink = init(wizard, 1);
+            mock::deploy(wizard, 10);
+            assert_eq!(Ok(0), magink.total_supply());
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Ok(()), magink.mint_wizard());
+
+            assert_eq!(Ok(1), magink.total_supply());
+            assert_eq!(Some(accounts.alice), mock::owner_of(wizard, Id::U64(1)));
+
+            match recorded_events().last() {
+                Some(Event::WizardMinted(event)) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.token_id, Id::U64(1));
+                }
+                _ => panic!("expected WizardMinted event"),
+            }
+        }
+
+        #[ink::test]
+        fn mint_wizard_assigns_sequential_token_ids() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            let mut magink = init(wizard, 1);
+            mock::deploy(wizard, 10);
+
+            for (account, token_id) in [
+                (accounts.alice, 1),
+                (accounts.bob, 2),
+                (accounts.charlie, 3),
+            ] {
+                set_sender(account);
+                assert_eq!(Ok(()), magink.start());
+                claim_badges(&mut magink, 1);
+                assert_eq!(Ok(()), magink.mint_wizard());
+
+                assert_eq!(Some(Id::U64(token_id)), magink.minted_token_of(account));
+                assert_eq!(Some(account), mock::owner_of(wizard, Id::U64(token_id)));
+            }
+
+            assert_eq!(Ok(3), magink.total_supply());
+        }
+
+        #[ink::test]
+        fn mint_wizard_skips_ids_minted_directly() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            let mut magink = init(wizard, 1);
+            mock::deploy(wizard, 10);
+
+            // another minter mints the ids Magink would pick next
+            mock::mint_directly(wizard, accounts.django, Id::U64(1));
+            mock::mint_directly(wizard, accounts.django, Id::U64(3));
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Ok(()), magink.mint_wizard());
+
+            assert_eq!(Some(Id::U64(4)), magink.minted_token_of(accounts.alice));
+            assert_eq!(Some(accounts.alice), mock::owner_of(wizard, Id::U64(4)));
+
+            set_sender(accounts.bob);
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Ok(()), magink.mint_wizard());
+
+            assert_eq!(Some(Id::U64(5)), magink.minted_token_of(accounts.bob));
+        }
+
+        #[ink::test]
+        fn mint_wizard_propagates_collection_full() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            let mut magink = init(wizard, 1);
+            mock::deploy(wizard, 1);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Ok(()), magink.mint_wizard());
+
+            set_sender(accounts.bob);
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(
+                Err(Error::Wizard(PSP34Error::Custom(String::from(
+                    "CollectionFull"
+                )))),
+                magink.mint_wizard()
+            );
+
+            // nothing is recorded for the failed mint
+            assert!(!magink.has_minted(accounts.bob));
+            assert_eq!(Ok(1), magink.total_supply());
+        }
+
+        #[ink::test]
+        fn mint_wizard_with_wrong_address_fails() {
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            mock::deploy(AccountId::from([0x02; 32]), 10);
+
+            assert_eq!(Err(Error::WizardNotFound), magink.total_supply());
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Err(Error::WizardNotFound), magink.mint_wizard());
+            assert!(!magink.has_minted(default_accounts().alice));
+
+            // repointing to the deployed wizard fixes it
+            assert_eq!(
+                Ok(()),
+                magink.set_wizard_contract(AccountId::from([0x02; 32]))
+            );
+            assert_eq!(Ok(()), magink.mint_wizard());
+        }
+
+        #[ink::test]
+        fn mint_wizard_out_of_gas_fails() {
+            let wizard = AccountId::from([0x01; 32]);
+            let mut magink = init(wizard, 1);
+            mock::deploy(wizard, 10);
+            mock::set_gas_required(wizard, 1_000);
+
+            assert_eq!(Ok(()), magink.set_wizard_gas_limit(999));
+            assert_eq!(Err(Error::WizardTrapped), magink.total_supply());
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Err(Error::WizardTrapped), magink.mint_wizard());
+
+            assert_eq!(Ok(()), magink.set_wizard_gas_limit(1_000));
+            assert_eq!(Ok(()), magink.mint_wizard());
+        }
+
+        #[ink::test]
+        fn badges_required_follows_lessons() {
+            let mut magink = init(AccountId::from([0x01; 32]), 9);
+            assert_eq!(9, magink.get_badges_required());
+
+            assert_eq!(Ok(()), magink.retire_lesson(0));
+            assert_eq!(8, magink.get_badges_required());
+
+            assert_eq!(
+                Ok(9),
+                magink.add_lesson(Hash::from([0x09; 32]), String::from("Lesson 9"))
+            );
+            assert_eq!(9, magink.get_badges_required());
+        }
+
+        #[ink::test]
+        fn add_lesson_works() {
+            let accounts = default_accounts();
+            let mut magink = Magink::new(AccountId::from([0x01; 32]));
+            assert!(magink.get_lessons().is_empty());
+            assert_eq!(0, magink.get_badges_required());
+
+            assert_eq!(
+                Ok(0),
+                magink.add_lesson(Hash::from([0x10; 32]), String::from("ink!"))
+            );
+            assert_eq!(
+                Ok(1),
+                magink.add_lesson(Hash::from([0x11; 32]), String::from("Astar"))
+            );
+
+            let ink = Lesson {
+                id: 0,
+                content_hash: Hash::from([0x10; 32]),
+                title: String::from("ink!"),
+                retired: false,
+            };
+            assert_eq!(Some(ink.clone()), magink.get_lesson(0));
+            assert_eq!(None, magink.get_lesson(2));
+            assert_eq!(2, magink.get_lessons().len());
+            assert_eq!(ink, magink.get_lessons()[0]);
+
+            match recorded_events().last() {
+                Some(Event::LessonAdded(event)) => {
+                    assert_eq!(event.id, 1);
+                    assert_eq!(event.content_hash, Hash::from([0x11; 32]));
+                }
+                _ => panic!("expected LessonAdded event"),
+            }
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.add_lesson(Hash::from([0x12; 32]), String::from("PSP34")),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(2, magink.get_lessons().len());
+        }
+
+        #[ink::test]
+        fn add_lesson_beyond_limit_fails() {
+            let mut magink = init(AccountId::from([0x01; 32]), 0);
+            for id in 0..MAX_LESSONS {
+                assert_eq!(Ok(id), magink.add_lesson(Hash::default(), String::new()));
+            }
+
+            assert_eq!(
+                magink.add_lesson(Hash::default(), String::new()),
+                Err(Error::TooManyLessons)
+            );
+
+            // retired ids are not reused
+            assert_eq!(Ok(()), magink.retire_lesson(0));
+            assert_eq!(
+                magink.add_lesson(Hash::default(), String::new()),
+                Err(Error::TooManyLessons)
+            );
+        }
+
+        #[ink::test]
+        fn retire_lesson_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 3);
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.retire_lesson(1),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+
+            set_sender(accounts.alice);
+            assert_eq!(Ok(()), magink.retire_lesson(1));
+            assert_eq!(vec![0, 2], lesson_ids(&magink));
+            assert!(magink.get_lesson(1).unwrap().retired);
+
+            match recorded_events().last() {
+                Some(Event::LessonRetired(event)) => assert_eq!(event.id, 1),
+                _ => panic!("expected LessonRetired event"),
+            }
+
+            assert_eq!(Err(Error::LessonNotFound), magink.retire_lesson(1));
+            assert_eq!(Err(Error::LessonNotFound), magink.retire_lesson(3));
+        }
+
+        #[ink::test]
+        fn reorder_lessons_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 3);
+
+            assert_eq!(Ok(()), magink.reorder_lessons(vec![2, 0, 1]));
+            assert_eq!(vec![2, 0, 1], lesson_ids(&magink));
+
+            match recorded_events().last() {
+                Some(Event::LessonsReordered(event)) => {
+                    assert_eq!(event.order, vec![2, 0, 1])
+                }
+                _ => panic!("expected LessonsReordered event"),
+            }
+
+            // every active lesson exactly once
+            for order in [
+                vec![],
+                vec![2, 0],
+                vec![2, 0, 0],
+                vec![2, 0, 1, 1],
+                vec![2, 0, 3],
+            ] {
+                assert_eq!(
+                    Err(Error::InvalidLessonOrder),
+                    magink.reorder_lessons(order)
+                );
+            }
+
+            // retired lessons can't come back through a reorder
+            assert_eq!(Ok(()), magink.retire_lesson(0));
+            assert_eq!(
+                Err(Error::InvalidLessonOrder),
+                magink.reorder_lessons(vec![0, 1, 2])
+            );
+            assert_eq!(Ok(()), magink.reorder_lessons(vec![1, 2]));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.reorder_lessons(vec![2, 1]),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(vec![1, 2], lesson_ids(&magink));
+        }
+
+        #[ink::test]
+        fn claim_follows_lesson_order() {
+            let mut magink = init(AccountId::from([0x01; 32]), 3);
+            assert_eq!(Ok(()), magink.reorder_lessons(vec![2, 0, 1]));
+            assert_eq!(Ok(()), magink.start());
+
+            for expected in [2, 0, 1] {
+                claim_badges(&mut magink, 1);
+                match recorded_events().last() {
+                    Some(Event::BadgeClaimed(event)) => {
+                        assert_eq!(event.lesson_id, expected)
+                    }
+                    _ => panic!("expected BadgeClaimed event"),
+                }
+            }
+        }
+
+        #[ink::test]
+        fn claim_lesson_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 3);
+
+            assert_eq!(Err(Error::UserNotFound), magink.claim_lesson(1));
+            assert_eq!(Ok(()), magink.start());
+
+            // lessons can be completed out of the registry order
+            advance_block();
+            assert_eq!(Ok(()), magink.claim_lesson(2));
+            match recorded_events().last() {
+                Some(Event::BadgeClaimed(event)) => {
+                    assert_eq!(event.lesson_id, 2);
+                    assert_eq!(event.badges_claimed, 1);
+                }
+                _ => panic!("expected BadgeClaimed event"),
+            }
+
+            // the era applies between lessons
+            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim_lesson(0));
+
+            // a lesson counts once
+            advance_block();
+            assert_eq!(Err(Error::LessonAlreadyCompleted), magink.claim_lesson(2));
+            assert_eq!(Err(Error::LessonNotFound), magink.claim_lesson(3));
+
+            // claim picks the first lesson left
+            assert_eq!(Ok(()), magink.claim());
+            assert_eq!(vec![0, 2], magink.get_completed_lessons(accounts.alice));
+            assert_eq!(2, magink.get_badges());
+            assert!(magink.get_completed_lessons(accounts.bob).is_empty());
+        }
+
+        #[ink::test]
+        fn claim_lesson_of_retired_lesson_fails() {
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+            assert_eq!(Ok(()), magink.retire_lesson(0));
+            assert_eq!(Ok(()), magink.start());
+
+            advance_block();
+            assert_eq!(Err(Error::LessonNotFound), magink.claim_lesson(0));
+            assert_eq!(Ok(()), magink.claim_lesson(1));
+            assert_eq!(1, magink.get_badges());
+        }
+
+        #[ink::test]
+        fn mint_requires_every_active_lesson() {
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+            mock::deploy(AccountId::from([0x01; 32]), 10);
+            assert_eq!(Ok(()), magink.start());
+
+            advance_block();
+            assert_eq!(Ok(()), magink.claim_lesson(0));
+
+            // a completed lesson that got retired doesn't stand in for another
+            assert_eq!(Ok(()), magink.retire_lesson(0));
+            assert_eq!(
+                Ok(2),
+                magink.add_lesson(Hash::from([0x02; 32]), String::from("Lesson 2"))
+            );
+            advance_block();
+            assert_eq!(Ok(()), magink.claim_lesson(1));
+            assert_eq!(2, magink.get_badges());
+            assert_eq!(Err(Error::NotAllBadgesCollected), magink.mint_wizard());
+
+            advance_block();
+            assert_eq!(Ok(()), magink.claim_lesson(2));
+            assert_eq!(Ok(()), magink.mint_wizard());
+        }
+
+        #[ink::test]
+        fn claim_mints_badge_token() {
+            let accounts = default_accounts();
+            let badge = AccountId::from([0x02; 32]);
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+            mock::deploy_badge(badge);
+
+            assert_eq!(None, magink.get_badge_contract());
+            assert_eq!(Ok(()), magink.set_badge_contract(badge));
+            assert_eq!(Some(badge), magink.get_badge_contract());
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+
+            assert_eq!(Some(Id::U64(1)), magink.badge_token_of(accounts.alice, 0));
+            assert_eq!(Some((accounts.alice, 0)), mock::badge_of(badge, Id::U64(1)));
+
+            let events = recorded_events();
+            match &events[events.len() - 2] {
+                Event::BadgeClaimed(event) => assert_eq!(event.lesson_id, 0),
+                _ => panic!("expected BadgeClaimed event"),
+            }
+            match &events[events.len() - 1] {
+                Event::BadgeMinted(event) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.lesson_id, 0);
+                    assert_eq!(event.token_id, Id::U64(1));
+                }
+                _ => panic!("expected BadgeMinted event"),
+            }
+
+            advance_block();
+            assert_eq!(Ok(()), magink.claim_lesson(1));
+            assert_eq!(Some(Id::U64(2)), magink.badge_token_of(accounts.alice, 1));
+            assert_eq!(None, magink.badge_token_of(accounts.bob, 1));
+        }
+
+        #[ink::test]
+        fn badge_token_survives_restart() {
+            let accounts = default_accounts();
+            let badge = AccountId::from([0x02; 32]);
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            mock::deploy_badge(badge);
+            assert_eq!(Ok(()), magink.set_badge_contract(badge));
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
+            assert_eq!(Ok(()), magink.restart(1));
+
+            // the lesson counts again, the token isn't minted twice
+            claim_badges(&mut magink, 1);
+            assert_eq!(1, magink.get_badges());
+            assert_eq!(Some(Id::U64(1)), magink.badge_token_of(accounts.alice, 0));
+            assert_eq!(None, mock::badge_of(badge, Id::U64(2)));
+
+            match recorded_events().last() {
+                Some(Event::BadgeClaimed(event)) => assert_eq!(event.lesson_id, 0),
+                _ => panic!("expected BadgeClaimed event"),
+            }
+        }
+
+        #[ink::test]
+        fn claim_fails_without_badge_contract() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            assert_eq!(
+                Ok(()),
+                magink.set_badge_contract(AccountId::from([0x02; 32]))
+            );
+
+            assert_eq!(Ok(()), magink.start());
+            advance_block();
+            assert_eq!(Err(Error::BadgeNotFound), magink.claim());
+            assert_eq!(None, magink.badge_token_of(accounts.alice, 0));
+        }
+
+        #[ink::test]
+        fn claim_fails_without_badge_minter_role() {
+            let accounts = default_accounts();
+            let badge = AccountId::from([0x02; 32]);
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            mock::deploy_badge(badge);
+            mock::revoke_badge_minter(badge);
+            assert_eq!(Ok(()), magink.set_badge_contract(badge));
+
+            assert_eq!(Ok(()), magink.start());
+            advance_block();
+            assert_eq!(
+                Err(Error::Badge(PSP34Error::Custom(String::from(
+                    "AC::MissingRole"
+                )))),
+                magink.claim()
+            );
+            assert_eq!(None, magink.badge_token_of(accounts.alice, 0));
+            assert_eq!(None, mock::badge_of(badge, Id::U64(1)));
+
+            // the failed mint leaves the profile untouched
+            assert_eq!(0, magink.get_badges());
+            assert!(magink.get_completed_lessons(accounts.alice).is_empty());
+        }
+
+        #[ink::test]
+        fn claim_badge_out_of_gas_fails() {
+            let accounts = default_accounts();
+            let badge = AccountId::from([0x02; 32]);
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            mock::deploy_badge(badge);
+            mock::set_badge_gas_required(badge, 1_000);
+            assert_eq!(Ok(()), magink.set_badge_contract(badge));
+
+            assert_eq!(Ok(()), magink.start());
+            advance_block();
+            assert_eq!(Ok(()), magink.set_badge_gas_limit(999));
+            assert_eq!(Err(Error::BadgeTrapped), magink.claim());
+
+            assert_eq!(Ok(()), magink.set_badge_gas_limit(1_000));
+            assert_eq!(Ok(()), magink.claim());
+            assert_eq!(Some(Id::U64(1)), magink.badge_token_of(accounts.alice, 0));
+        }
+
+        #[ink::test]
+        fn set_badge_gas_limit_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            assert_eq!(DEFAULT_BADGE_GAS_LIMIT, magink.get_badge_gas_limit());
+
+            assert_eq!(Ok(()), magink.set_badge_gas_limit(5_000_000_000));
+            assert_eq!(5_000_000_000, magink.get_badge_gas_limit());
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.set_badge_gas_limit(1),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(5_000_000_000, magink.get_badge_gas_limit());
+        }
+
+        #[ink::test]
+        fn set_badge_contract_only_owner() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 0);
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.set_badge_contract(AccountId::from([0x02; 32])),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(None, magink.get_badge_contract());
+        }
+
+        #[ink::test]
+        fn set_quiz_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+            let quiz = Quiz {
+                salt: Hash::from([0x07; 32]),
+                answer_hash: answer_hash(&Hash::from([0x07; 32]), "ink!"),
+            };
+
+            assert_eq!(None, magink.get_quiz(0));
+            assert_eq!(Ok(()), magink.set_quiz(0, quiz.salt, quiz.answer_hash));
+            assert_eq!(Some(quiz.clone()), magink.get_quiz(0));
+
+            match recorded_events().last() {
+                Some(Event::QuizSet(event)) => assert_eq!(event.lesson_id, 0),
+                _ => panic!("expected QuizSet event"),
+            }
+
+            assert_eq!(
+                Err(Error::LessonNotFound),
+                magink.set_quiz(2, quiz.salt, quiz.answer_hash)
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.set_quiz(1, quiz.salt, quiz.answer_hash),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(None, magink.get_quiz(1));
+        }
+
+        #[ink::test]
+        fn answer_hash_is_salted() {
+            let salt = Hash::from([0x07; 32]);
+
+            assert_eq!(answer_hash(&salt, "ink!"), answer_hash(&salt, "ink!"));
+            assert_ne!(answer_hash(&salt, "ink!"), answer_hash(&salt, "ink"));
+            assert_ne!(
+                answer_hash(&salt, "ink!"),
+                answer_hash(&Hash::from([0x08; 32]), "ink!")
+            );
+        }
+
+        #[ink::test]
+        fn reveal_right_answer_works() {
+            let accounts = default_accounts();
+            let mut magink = init_with_quiz(2);
+            assert_eq!(Ok(()), magink.start());
+
+            // the quiz has to be answered
+            advance_block();
+            assert_eq!(Err(Error::AnswerRequired), magink.claim());
+            assert_eq!(Err(Error::AnswerRequired), magink.claim_lesson(0));
+
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            assert_eq!(
+                Some(Commitment {
+                    hash: commitment_hash("ink!", &Hash::from(SECRET), &accounts.alice),
+                    block: 1,
+                }),
+                magink.get_commitment(accounts.alice, 0)
+            );
+            match recorded_events().last() {
+                Some(Event::AnswerCommitted(event)) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.lesson_id, 0);
+                    assert_eq!(event.block, 1);
+                }
+                _ => panic!("expected AnswerCommitted event"),
+            }
+
+            // not in the commit block
+            assert_eq!(Err(Error::RevealTooEarly), reveal(&mut magink, "ink!"));
+
+            advance_block();
+            assert_eq!(Ok(true), reveal(&mut magink, "ink!"));
+            assert_eq!(vec![0], magink.get_completed_lessons(accounts.alice));
+            assert_eq!(None, magink.get_commitment(accounts.alice, 0));
+
+            let events = recorded_events();
+            match &events[events.len() - 2] {
+                Event::AnswerSubmitted(event) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.lesson_id, 0);
+                    assert!(event.correct);
+                    assert_eq!(event.locked_until, None);
+                }
+                _ => panic!("expected AnswerSubmitted event"),
+            }
+            match &events[events.len() - 1] {
+                Event::BadgeClaimed(event) => assert_eq!(event.lesson_id, 0),
+                _ => panic!("expected BadgeClaimed event"),
+            }
+
+            // lessons without a quiz take no answer
+            assert_eq!(
+                Err(Error::NoQuiz),
+                magink.commit_answer(1, Hash::from([0x01; 32]))
+            );
+            advance_block();
+            assert_eq!(Ok(()), magink.claim_lesson(1));
+        }
+
+        #[ink::test]
+        fn reveal_wrong_answer_locks_out() {
+            let accounts = default_accounts();
+            let mut magink = init_with_quiz(1);
+            assert_eq!(Ok(()), magink.set_answer_lockout(3));
+            assert_eq!(3, magink.get_answer_lockout());
+            assert_eq!(Ok(()), magink.start());
+
+            assert_eq!(Ok(()), commit(&mut magink, "solidity"));
+            advance_block();
+            assert_eq!(Ok(false), reveal(&mut magink, "solidity"));
+            assert_eq!(Some(4), magink.get_locked_until(accounts.alice, 0));
+            assert_eq!(None, magink.get_commitment(accounts.alice, 0));
+            assert_eq!(0, magink.get_badges());
+
+            match recorded_events().last() {
+                Some(Event::AnswerSubmitted(event)) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.lesson_id, 0);
+                    assert!(!event.correct);
+                    assert_eq!(event.locked_until, Some(4));
+                }
+                _ => panic!("expected AnswerSubmitted event"),
+            }
+
+            // even the right answer waits for the lockout
+            advance_n_blocks(2);
+            assert_eq!(Err(Error::LessonLocked), commit(&mut magink, "ink!"));
+
+            advance_block();
+            assert_eq!(None, magink.get_locked_until(accounts.alice, 0));
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            advance_block();
+            assert_eq!(Ok(true), reveal(&mut magink, "ink!"));
+            assert_eq!(1, magink.get_badges());
+        }
+
+        #[ink::test]
+        fn reveal_must_match_commitment() {
+            let accounts = default_accounts();
+            let mut magink = init_with_quiz(1);
+            assert_eq!(Ok(()), magink.start());
+
+            assert_eq!(Err(Error::CommitmentNotFound), reveal(&mut magink, "ink!"));
+
+            // a copied commitment is bound to the account that made it
+            let commitment = commitment_hash("ink!", &Hash::from(SECRET), &accounts.bob);
+            assert_eq!(Ok(()), magink.commit_answer(0, commitment));
+            advance_block();
+            assert_eq!(Err(Error::CommitmentMismatch), reveal(&mut magink, "ink!"));
+
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            advance_block();
+            assert_eq!(
+                Err(Error::CommitmentMismatch),
+                magink.reveal_answer(0, String::from("ink!"), Hash::from([0x43; 32]))
+            );
+            assert_eq!(
+                Err(Error::CommitmentMismatch),
+                reveal(&mut magink, "solidity")
+            );
+            assert_eq!(Ok(true), reveal(&mut magink, "ink!"));
+        }
+
+        #[ink::test]
+        fn unrevealed_commitment_expires() {
+            let accounts = default_accounts();
+            let mut magink = init_with_quiz(1);
+            assert_eq!(Ok(()), magink.set_reveal_window(2));
+            assert_eq!(2, magink.get_reveal_window());
+            assert_eq!(Ok(()), magink.start());
+
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            advance_n_blocks(2);
+            assert!(magink.get_commitment(accounts.alice, 0).is_some());
+
+            advance_block();
+            assert_eq!(None, magink.get_commitment(accounts.alice, 0));
+            assert_eq!(Err(Error::CommitmentExpired), reveal(&mut magink, "ink!"));
+
+            // committing again opens a new window
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            advance_n_blocks(2);
+            assert_eq!(Ok(true), reveal(&mut magink, "ink!"));
+        }
+
+        #[ink::test]
+        fn reveal_waits_for_the_era() {
+            let accounts = default_accounts();
+            let mut magink = init_with_quiz(2);
+            let salt = Hash::from([0x07; 32]);
+            assert_eq!(Ok(()), magink.set_quiz(1, salt, answer_hash(&salt, "ink!")));
+            assert_eq!(Ok(()), magink.set_era_length(5));
+            assert_eq!(Ok(()), magink.start());
+
+            // the era applies to the first lesson
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            advance_block();
+            assert_eq!(Err(Error::TooEarlyToClaim), reveal(&mut magink, "ink!"));
+
+            advance_n_blocks(4);
+            assert_eq!(Ok(true), reveal(&mut magink, "ink!"));
+
+            // and between lessons
+            let commitment =
+                commitment_hash("ink!", &Hash::from(SECRET), &accounts.alice);
+            assert_eq!(Ok(()), magink.commit_answer(1, commitment));
+            advance_block();
+            assert_eq!(
+                Err(Error::TooEarlyToClaim),
+                magink.reveal_answer(1, String::from("ink!"), Hash::from(SECRET))
+            );
+
+            advance_n_blocks(4);
+            assert_eq!(
+                Ok(true),
+                magink.reveal_answer(1, String::from("ink!"), Hash::from(SECRET))
+            );
+            assert_eq!(2, magink.get_badges());
+        }
+
+        #[ink::test]
+        fn set_reveal_window_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 0);
+            assert_eq!(DEFAULT_REVEAL_WINDOW, magink.get_reveal_window());
+
+            assert_eq!(Err(Error::InvalidRevealWindow), magink.set_reveal_window(0));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.set_reveal_window(1),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(DEFAULT_REVEAL_WINDOW, magink.get_reveal_window());
+        }
+
+        #[ink::test]
+        fn commitment_hash_binds_every_part() {
+            let accounts = default_accounts();
+            let hash = commitment_hash("ink!", &Hash::from(SECRET), &accounts.alice);
+
+            assert_eq!(
+                hash,
+                commitment_hash("ink!", &Hash::from(SECRET), &accounts.alice)
+            );
+            assert_ne!(
+                hash,
+                commitment_hash("ink", &Hash::from(SECRET), &accounts.alice)
+            );
+            assert_ne!(
+                hash,
+                commitment_hash("ink!", &Hash::from([0x43; 32]), &accounts.alice)
+            );
+            assert_ne!(
+                hash,
+                commitment_hash("ink!", &Hash::from(SECRET), &accounts.bob)
+            );
+        }
+
+        #[ink::test]
+        fn set_answer_lockout_only_owner() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 0);
+            assert_eq!(DEFAULT_ANSWER_LOCKOUT, magink.get_answer_lockout());
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.set_answer_lockout(0),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(DEFAULT_ANSWER_LOCKOUT, magink.get_answer_lockout());
+        }
+
+        #[test]
+        fn completed_lessons_cover_every_bit() {
+            let mut profile = Profile {
+                claim_era: 1,
+                start_block: 0,
+                completed_lessons: 0,
+                previous_badges_claimed: 0,
+            };
+
+            for id in [0, 1, 63, 64, MAX_LESSONS - 1] {
+                assert!(!profile.has_completed(id));
+                profile.completed_lessons |= 1 << id;
+                assert!(profile.has_completed(id));
+            }
+
+            assert_eq!(5, profile.badges_claimed());
+            assert_eq!(
+                vec![0, 1, 63, 64, MAX_LESSONS - 1],
+                profile.completed_lessons()
+            );
+        }
+
+        #[ink::test]
+        fn mint_without_lessons_fails() {
+            let mut magink = init(AccountId::from([0x01; 32]), 0);
+            mock::deploy(AccountId::from([0x01; 32]), 10);
+
+            assert_eq!(Ok(()), magink.start());
+            advance_block();
+            assert_eq!(Err(Error::TooManyBadges), magink.claim());
+            assert_eq!(Err(Error::NoLessons), magink.mint_wizard());
+        }
+
+        #[ink::test]
+        fn default_era_prevents_claims_every_block() {
+            let mut magink = Magink::new(AccountId::from([0x01; 32]));
+            assert!(magink
+                .insert_lesson(Hash::default(), String::from("Lesson 0"))
+                .is_ok());
+            assert_eq!(DEFAULT_ERA_LENGTH, magink.get_era_length());
+
+            assert_eq!(Ok(()), magink.start());
+            assert_eq!(DEFAULT_ERA_LENGTH, magink.get_remaining());
+
+            advance_block();
+            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+
+            advance_n_blocks(DEFAULT_ERA_LENGTH - 1);
+            assert_eq!(Ok(()), magink.claim());
+        }
+
+        #[test]
+        fn wizard_call_errors_are_typed() {
+            use ink::env::Error as EnvError;
+
+            assert_eq!(Ok(1), wizard_call_result::<Balance>(Ok(Ok(1))));
+            assert_eq!(
+                Err(Error::WizardLangError(ink::LangError::CouldNotReadInput)),
+                wizard_call_result::<Balance>(Ok(Err(ink::LangError::CouldNotReadInput)))
+            );
+
+            // wrong address or missing contract
+            assert_eq!(
+                Err(Error::WizardNotFound),
+                wizard_call_result::<Balance>(Err(EnvError::CodeNotFound))
+            );
+            assert_eq!(
+                Err(Error::WizardNotFound),
+                wizard_call_result::<Balance>(Err(EnvError::NotCallable))
+            );
+
+            // out of gas traps the callee
+            assert_eq!(
+                Err(Error::WizardTrapped),
+                wizard_call_result::<Balance>(Err(EnvError::CalleeTrapped))
+            );
+            assert_eq!(
+                Err(Error::WizardReverted),
+                wizard_call_result::<Balance>(Err(EnvError::CalleeReverted))
+            );
+
+            for error in [
+                EnvError::KeyNotFound,
+                EnvError::TransferFailed,
+                EnvError::Unknown,
+            ] {
+                assert_eq!(
+                    Err(Error::CrossContractCallFailed),
+                    wizard_call_result::<Balance>(Err(error))
+                );
+            }
+
+            // errors returned by the wizard itself are passed through
+            let minted: Result<(), Error> = wizard_call_result(Ok(Ok(Err(
+                PSP34Error::Custom(String::from("CollectionFull")),
+            ))))
+            .and_then(|result| result.map_err(Error::from));
+
+            assert_eq!(
+                Err(Error::Wizard(PSP34Error::Custom(String::from(
+                    "CollectionFull"
+                )))),
+                minted
+            );
+        }
+
+        #[ink::test]
+        fn set_wizard_gas_limit_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            assert_eq!(DEFAULT_WIZARD_GAS_LIMIT, magink.get_wizard_gas_limit());
+
+            assert_eq!(Ok(()), magink.set_wizard_gas_limit(5_000_000_000));
+            assert_eq!(5_000_000_000, magink.get_wizard_gas_limit());
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.set_wizard_gas_limit(1),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(5_000_000_000, magink.get_wizard_gas_limit());
+        }
+
+        #[ink::test]
+        fn claim_without_profile_fails() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+
+            // unknown accounts have no remaining blocks, but can't claim either
+            assert_eq!(0, magink.get_remaining());
+            assert_eq!(Err(Error::UserNotFound), magink.claim());
+            assert_eq!(None, magink.get_account_profile(accounts.alice));
+            assert!(recorded_events().is_empty());
+        }
+
+        #[ink::test]
+        fn era_longer_than_u8_works() {
+            const ERA: u32 = 300;
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+
+            assert_eq!(Ok(()), magink.set_era_length(ERA));
+            assert_eq!(Ok(()), magink.start());
+            assert_eq!(ERA, magink.get_remaining());
+
+            advance_n_blocks(ERA - 1);
+            assert_eq!(1, magink.get_remaining());
+            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+
+            advance_block();
+            assert_eq!(0, magink.get_remaining());
+            assert_eq!(Ok(()), magink.claim());
+            assert_eq!(ERA, magink.get_remaining());
+        }
+
+        #[ink::test]
+        fn claim_beyond_badge_limit_fails() {
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 2);
+
+            // one badge per active lesson
+            advance_block();
+            assert_eq!(Err(Error::TooManyBadges), magink.claim());
+            assert_eq!(2, magink.get_badges());
+
+            assert_eq!(
+                Ok(2),
+                magink.add_lesson(Hash::from([0x02; 32]), String::from("Lesson 2"))
+            );
+            assert_eq!(Ok(()), magink.claim());
+            assert_eq!(3, magink.get_badges());
+        }
+
+        #[test]
+        fn remaining_never_wraps() {
+            let eras = [0, 1, 255, 256, 1_000, 65_536, u32::MAX / 2, u32::MAX];
+            let starts = [0, 1, 255, 100_000, u32::MAX / 2, u32::MAX - 1, u32::MAX];
+
+            // deterministic xorshift so failures are reproducible
+            let mut seed: u32 = 0x9e37_79b9;
+            let mut next = move || {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                seed
+            };
+
+            for &claim_era in eras.iter() {
+                for &start_block in starts.iter() {
+                    let profile = Profile {
+                        claim_era,
+                        start_block,
+                        completed_lessons: 0,
+                        previous_badges_claimed: 0,
+                    };
+
+                    let mut previous = claim_era;
+                    let mut offsets = vec![0, 1, claim_era.saturating_sub(1), claim_era];
+                    offsets.extend((0..64).map(|_| next()));
+                    offsets.sort_unstable();
+
+                    for elapsed in offsets {
+                        let current_block = start_block.saturating_add(elapsed);
+                        let elapsed = current_block - start_block;
+                        let remaining = profile.remaining_at(current_block);
+
+                        assert!(remaining <= claim_era);
+                        assert!(remaining <= previous, "remaining must not grow");
+                        assert_eq!(remaining == 0, elapsed >= claim_era);
+                        if elapsed < claim_era {
+                            assert_eq!(remaining + elapsed, claim_era);
+                        }
+
+                        previous = remaining;
+                    }
+
+                    // a block before the start never underflows
+                    if start_block > 0 {
+                        assert_eq!(profile.remaining_at(start_block - 1), claim_era);
+                    }
+                }
+            }
+        }
+
+        #[ink::test]
+        fn start_twice_fails() {
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 2);
+
+            assert_eq!(Err(Error::AlreadyStarted), magink.start());
+            assert_eq!(2, magink.get_badges());
+        }
+
+        #[ink::test]
+        fn restart_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+
+            // nothing to restart
+            assert_eq!(Err(Error::UserNotFound), magink.restart(0));
+
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 2);
+
+            // the badge count has to be confirmed
+            assert_eq!(Err(Error::RestartNotConfirmed), magink.restart(0));
+            assert_eq!(Err(Error::RestartNotConfirmed), magink.restart(3));
+            assert_eq!(2, magink.get_badges());
+
+            // the restart uses the era length of the moment
+            assert_eq!(Ok(()), magink.set_era_length(5));
+            assert_eq!(Ok(()), magink.restart(2));
+            assert_eq!(0, magink.get_badges());
+            assert_eq!(5, magink.get_remaining());
+
+            let profile = magink.get_profile().unwrap();
+            assert_eq!(0, profile.badges_claimed());
+            assert_eq!(2, profile.previous_badges_claimed);
+            assert!(magink.get_completed_lessons(accounts.alice).is_empty());
+
+            match recorded_events().last() {
+                Some(Event::Restarted(event)) => {
+                    assert_eq!(event.account, accounts.alice);
+                    assert_eq!(event.era, 5);
+                    assert_eq!(event.start_block, 2);
+                    assert_eq!(event.previous_badges_claimed, 2);
+                }
+                _ => panic!("expected Restarted event"),
+            }
+        }
+
+        #[ink::test]
+        fn pause_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            assert!(!Pausable::paused(&magink));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.pause(),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+
+            set_sender(accounts.alice);
+            assert_eq!(Ok(()), magink.pause());
+            assert!(Pausable::paused(&magink));
+            assert_eq!(magink.pause(), Err(Error::Pausable(PausableError::Paused)));
+
+            match recorded_events().last() {
+                Some(Event::Paused(event)) => assert_eq!(event.account, accounts.alice),
+                _ => panic!("expected Paused event"),
+            }
 
-            let account_id = client
-                .instantiate("wizard", &ink_e2e::bob(), constructor, 0, None)
-                .await
-                .expect("instantiate failed")
-                .account_id;
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.unpause(),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
 
-            let owner = {
-                let msg = build_message::<WizardRef>(account_id.clone())
-                    .call(|contract| contract.owner());
-                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            set_sender(accounts.alice);
+            assert_eq!(Ok(()), magink.unpause());
+            assert!(!Pausable::paused(&magink));
+            assert_eq!(
+                magink.unpause(),
+                Err(Error::Pausable(PausableError::NotPaused))
+            );
+
+            match recorded_events().last() {
+                Some(Event::Unpaused(event)) => assert_eq!(event.account, accounts.alice),
+                _ => panic!("expected Unpaused event"),
             }
-            .return_value();
+        }
 
-            assert_eq!(owner, Some(address_of!(bob)));
-            Ok(())
+        #[ink::test]
+        fn start_and_restart_fail_while_paused() {
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+
+            assert_eq!(Ok(()), magink.pause());
+            assert_eq!(magink.start(), Err(Error::Pausable(PausableError::Paused)));
+            assert_eq!(None, magink.get_profile());
+
+            assert_eq!(Ok(()), magink.unpause());
+            assert_eq!(Ok(()), magink.start());
+
+            assert_eq!(Ok(()), magink.pause());
+            assert_eq!(
+                magink.restart(0),
+                Err(Error::Pausable(PausableError::Paused))
+            );
+            assert_eq!(1, magink.get_remaining());
+
+            assert_eq!(Ok(()), magink.unpause());
+            assert_eq!(Ok(()), magink.restart(0));
         }
 
-        #[ink_e2e::test]
-        async fn e2e_mint_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
-            let max_supply: u64 = 10;
+        #[ink::test]
+        fn claim_fails_while_paused() {
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            assert_eq!(Ok(()), magink.start());
+            advance_block();
 
-            // instantiate wizard contract
-            let wizard_constructor = WizardRef::new(max_supply);
+            assert_eq!(Ok(()), magink.pause());
+            assert_eq!(magink.claim(), Err(Error::Pausable(PausableError::Paused)));
+            assert_eq!(0, magink.get_badges());
 
-            let wizard_account_id = client
-                .instantiate("wizard", &ink_e2e::alice(), wizard_constructor, 0, None)
-                .await
-                .expect("wizard contract instantiate failed")
-                .account_id;
+            assert_eq!(Ok(()), magink.unpause());
+            assert_eq!(Ok(()), magink.claim());
+            assert_eq!(1, magink.get_badges());
+        }
 
-            // instantiate magink contract
-            let magink_constructor = MaginkRef::new(wizard_account_id);
+        #[ink::test]
+        fn mint_wizard_fails_while_paused() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            mock::deploy(wizard, 10);
 
-            let magink_account_id = client
-                .instantiate("magink", &ink_e2e::alice(), magink_constructor, 0, None)
-                .await
-                .expect("magink contract instantiate failed")
-                .account_id;
+            let mut magink = init(wizard, 1);
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
 
-            // transfer ownership to magink
-            let change_owner = build_message::<WizardRef>(wizard_account_id.clone())
-                .call(|p| p.transfer_ownership(magink_account_id));
+            assert_eq!(Ok(()), magink.pause());
+            assert_eq!(
+                magink.mint_wizard(),
+                Err(Error::Pausable(PausableError::Paused))
+            );
+            assert!(!magink.has_minted(accounts.alice));
+            assert_eq!(Ok(0), magink.total_supply());
 
-            client
-                .call(&ink_e2e::alice(), change_owner, 0, None)
-                .await
-                .expect("calling transfer_ownership failed");
+            assert_eq!(Ok(()), magink.unpause());
+            assert_eq!(Ok(()), magink.mint_wizard());
+            assert_eq!(Some(Id::U64(1)), magink.minted_token_of(accounts.alice));
+        }
 
-            // verfy it
-            let owner =
-                build_message::<WizardRef>(wizard_account_id.clone()).call(|p| p.owner());
+        #[ink::test]
+        fn claim_lesson_fails_while_paused() {
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+            assert_eq!(Ok(()), magink.start());
+            advance_block();
 
-            let owner_result = client
-                .call_dry_run(&ink_e2e::alice(), &owner, 0, None)
-                .await
-                .return_value()
-                .unwrap();
+            assert_eq!(Ok(()), magink.pause());
+            assert_eq!(
+                magink.claim_lesson(1),
+                Err(Error::Pausable(PausableError::Paused))
+            );
+            assert_eq!(0, magink.get_badges());
 
-            assert_eq!(owner_result, magink_account_id);
+            assert_eq!(Ok(()), magink.unpause());
+            assert_eq!(Ok(()), magink.claim_lesson(1));
+            assert_eq!(1, magink.get_badges());
+        }
 
-            // check total supply
-            let total_supply = {
-                let msg = build_message::<MaginkRef>(magink_account_id.clone())
-                    .call(|contract| contract.total_supply());
+        #[ink::test]
+        fn commit_answer_fails_while_paused() {
+            let accounts = default_accounts();
+            let mut magink = init_with_quiz(1);
+            assert_eq!(Ok(()), magink.start());
 
-                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
-            }
-            .return_value();
+            assert_eq!(Ok(()), magink.pause());
+            assert_eq!(
+                commit(&mut magink, "ink!"),
+                Err(Error::Pausable(PausableError::Paused))
+            );
+            assert_eq!(None, magink.get_commitment(accounts.alice, 0));
 
-            assert_eq!(total_supply, 0);
+            assert_eq!(Ok(()), magink.unpause());
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            assert!(magink.get_commitment(accounts.alice, 0).is_some());
+        }
 
-            // start
-            let start_msg = build_message::<MaginkRef>(magink_account_id.clone())
-                .call(|magink| magink.start(0));
+        #[ink::test]
+        fn reveal_answer_fails_while_paused() {
+            let mut magink = init_with_quiz(1);
+            assert_eq!(Ok(()), magink.start());
+            assert_eq!(Ok(()), commit(&mut magink, "ink!"));
+            advance_block();
 
-            client
-                .call(&ink_e2e::alice(), start_msg, 0, None)
-                .await
-                .expect("calling start failed");
+            assert_eq!(Ok(()), magink.pause());
+            assert_eq!(
+                reveal(&mut magink, "ink!"),
+                Err(Error::Pausable(PausableError::Paused))
+            );
+            assert_eq!(0, magink.get_badges());
 
-            // claim
-            let claim_msg = build_message::<MaginkRef>(magink_account_id.clone())
-                .call(|magink| magink.claim());
+            assert_eq!(Ok(()), magink.unpause());
+            assert_eq!(Ok(true), reveal(&mut magink, "ink!"));
+            assert_eq!(1, magink.get_badges());
+        }
 
-            client
-                .call(&ink_e2e::alice(), claim_msg, 0, None)
-                .await
-                .expect("calling claim failed");
+        #[ink::test]
+        fn upgrade_checks_owner() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
 
-            let badges = {
-                let msg = build_message::<MaginkRef>(magink_account_id.clone())
-                    .call(|magink| magink.get_badges());
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.upgrade(Hash::from([0x02; 32])),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+        }
 
-                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
-            }
-            .return_value();
+        #[ink::test]
+        fn migrate_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
+            assert_eq!(STORAGE_VERSION, magink.get_version());
+            assert_eq!(Err(Error::AlreadyMigrated), magink.migrate());
 
-            assert_eq!(badges, 1);
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 2);
+            let profile = magink.get_profile();
 
-            // mint new token
-            let mint_wizard_msg = build_message::<MaginkRef>(magink_account_id.clone())
-                .call(|magink| magink.mint_wizard());
+            // storage left behind by older code
+            magink.version.set(&(STORAGE_VERSION - 1));
 
-            client
-                .call(&ink_e2e::alice(), mint_wizard_msg, 0, None)
-                .await
-                .expect("minting new token failed");
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.migrate(),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
 
-            let total_supply = {
-                let msg = build_message::<MaginkRef>(magink_account_id.clone())
-                    .call(|contract| contract.total_supply());
+            set_sender(accounts.alice);
+            assert_eq!(Ok(()), magink.migrate());
+            assert_eq!(STORAGE_VERSION, magink.get_version());
+            assert_eq!(profile, magink.get_profile());
+            assert_eq!(2, magink.get_badges());
 
-                client.call_dry_run(&ink_e2e::alice(), &msg, 0, None).await
+            match recorded_events().last() {
+                Some(Event::Migrated(event)) => {
+                    assert_eq!(event.from_version, STORAGE_VERSION - 1);
+                    assert_eq!(event.to_version, STORAGE_VERSION);
+                }
+                _ => panic!("expected Migrated event"),
             }
-            .return_value();
+        }
 
-            assert_eq!(total_supply, 1);
+        #[ink::test]
+        fn status_of_unknown_account() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            mock::deploy(wizard, 10);
+            let magink = init(wizard, 3);
 
-            Ok(())
+            assert_eq!(
+                magink.status(accounts.bob),
+                Status {
+                    profile: None,
+                    remaining_blocks: 0,
+                    next_claim_block: None,
+                    badges_required: 3,
+                    can_mint: false,
+                    minted_token_id: None,
+                    remaining_supply: Some(10),
+                }
+            );
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+        #[ink::test]
+        fn status_tracks_progress() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            mock::deploy(wizard, 10);
+            let mut magink = init(wizard, 2);
+
+            assert_eq!(Ok(()), magink.set_era_length(5));
+            assert_eq!(Ok(()), magink.start());
+            advance_n_blocks(2);
+
+            let status = magink.status(accounts.alice);
+            assert_eq!(status.profile, magink.get_profile());
+            assert_eq!(status.remaining_blocks, 3);
+            assert_eq!(status.next_claim_block, Some(5));
+            assert!(!status.can_mint);
+
+            advance_n_blocks(3);
+            assert_eq!(Ok(()), magink.claim());
+            advance_n_blocks(5);
+            assert_eq!(Ok(()), magink.claim());
+
+            let status = magink.status(accounts.alice);
+            assert_eq!(status.next_claim_block, Some(15));
+            assert!(status.can_mint);
+            assert_eq!(status.minted_token_id, None);
+
+            assert_eq!(Ok(()), magink.mint_wizard());
+
+            let status = magink.status(accounts.alice);
+            assert!(!status.can_mint);
+            assert_eq!(status.minted_token_id, Some(Id::U64(1)));
+            assert_eq!(status.remaining_supply, Some(9));
+        }
 
         #[ink::test]
-        fn start_works() {
-            let mut magink = Magink::new(AccountId::from([0x01; 32]));
-            println!("get {:?}", magink.get_remaining());
+        fn status_checks_pause_and_supply() {
+            let accounts = default_accounts();
+            let wizard = AccountId::from([0x01; 32]);
+            mock::deploy(wizard, 1);
+            let mut magink = init(wizard, 1);
 
-            magink.start(10);
-            assert_eq!(10, magink.get_remaining());
+            assert_eq!(Ok(()), magink.start());
+            assert!(!magink.status(accounts.alice).can_mint);
 
-            advance_block();
-            assert_eq!(9, magink.get_remaining());
+            claim_badges(&mut magink, 1);
+            assert!(magink.status(accounts.alice).can_mint);
+
+            assert_eq!(Ok(()), magink.pause());
+            assert!(!magink.status(accounts.alice).can_mint);
+            assert_eq!(Ok(()), magink.unpause());
+
+            mock::mint_directly(wizard, accounts.bob, Id::U64(1));
+            let status = magink.status(accounts.alice);
+            assert_eq!(status.remaining_supply, Some(0));
+            assert!(!status.can_mint);
         }
 
         #[ink::test]
-        fn claim_works() {
-            const ERA: u32 = 10;
+        fn status_without_wizard() {
             let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 0);
+            assert_eq!(Ok(()), magink.start());
 
-            let mut magink = Magink::new(AccountId::from([0x01; 32]));
+            let status = magink.status(accounts.alice);
+            assert_eq!(status.remaining_supply, None);
+            assert_eq!(status.remaining_blocks, 1);
+        }
 
-            magink.start(ERA as u8);
+        #[ink::test]
+        fn batch_queries_of_empty_batch() {
+            let magink = init(AccountId::from([0x01; 32]), 1);
 
-            advance_n_blocks(ERA - 1);
-            assert_eq!(1, magink.get_remaining());
+            assert_eq!(Ok(Vec::new()), magink.get_profiles(Vec::new()));
+            assert_eq!(Ok(Vec::new()), magink.get_badges_many(Vec::new()));
+        }
 
-            // claim fails, too early
-            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+        #[ink::test]
+        fn batch_queries_keep_order() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 2);
 
-            // claim succeeds
-            advance_block();
-            assert_eq!(Ok(()), magink.claim());
-            assert_eq!(1, magink.get_badges());
-            assert_eq!(1, magink.get_badges_for(accounts.alice));
-            assert_eq!(1, magink.get_badges());
-            assert_eq!(10, magink.get_remaining());
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 2);
 
-            // claim fails, too early
-            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+            set_sender(accounts.charlie);
+            assert_eq!(Ok(()), magink.start());
+            claim_badges(&mut magink, 1);
 
-            advance_block();
-            assert_eq!(9, magink.get_remaining());
+            let batch =
+                vec![accounts.bob, accounts.charlie, accounts.alice, accounts.bob];
 
-            assert_eq!(Err(Error::TooEarlyToClaim), magink.claim());
+            assert_eq!(
+                Ok(vec![
+                    None,
+                    magink.get_account_profile(accounts.charlie),
+                    magink.get_account_profile(accounts.alice),
+                    None,
+                ]),
+                magink.get_profiles(batch.clone())
+            );
+            assert_eq!(Ok(vec![0, 1, 2, 0]), magink.get_badges_many(batch));
         }
 
         #[ink::test]
-        fn mint_check_works_offchain_contract_call_fails() {
-            const ERA: u32 = 3;
-            let mut magink = Magink::new(AccountId::from([0x01; 32]));
+        fn batch_queries_check_size() {
+            let magink = init(AccountId::from([0x01; 32]), 1);
 
-            magink.start(ERA as u8);
+            let batch = vec![AccountId::from([0x02; 32]); MAX_BATCH_SIZE as usize];
+            assert_eq!(
+                Ok(vec![None; MAX_BATCH_SIZE as usize]),
+                magink.get_profiles(batch.clone())
+            );
+            assert_eq!(
+                Ok(vec![0; MAX_BATCH_SIZE as usize]),
+                magink.get_badges_many(batch)
+            );
 
-            assert_eq!(3, magink.get_remaining());
-            assert_eq!(0, magink.get_badges());
+            let batch = vec![AccountId::from([0x02; 32]); MAX_BATCH_SIZE as usize + 1];
+            assert_eq!(
+                Err(Error::BatchTooLarge),
+                magink.get_profiles(batch.clone())
+            );
+            assert_eq!(Err(Error::BatchTooLarge), magink.get_badges_many(batch));
+        }
 
-            advance_block();
-            assert_eq!(2, magink.get_remaining());
-            advance_block();
+        #[ink::test]
+        fn set_era_length_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+
+            // a zero era would allow claiming every block
+            assert_eq!(Err(Error::InvalidEraLength), magink.set_era_length(0));
+            assert_eq!(1, magink.get_era_length());
+
+            assert_eq!(Ok(()), magink.start());
+            assert_eq!(Ok(()), magink.set_era_length(7));
+            assert_eq!(7, magink.get_era_length());
+
+            // running eras keep their length
             assert_eq!(1, magink.get_remaining());
-            advance_block();
-            assert_eq!(0, magink.get_remaining());
 
-            // mint wizard returns error since not all badges are collected
-            assert!(magink.mint_wizard().is_err());
+            set_sender(accounts.bob);
             assert_eq!(
-                magink.mint_wizard(),
-                Err(PSP34Error::Custom(String::from("NotAllBadgesCollected")))
+                magink.set_era_length(1),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
             );
+            assert_eq!(7, magink.get_era_length());
 
-            assert_eq!(Ok(()), magink.claim());
-            assert_eq!(3, magink.get_remaining());
-            assert_eq!(1, magink.get_badges());
+            assert_eq!(Ok(()), magink.start());
+            assert_eq!(7, magink.get_remaining());
+        }
 
-            advance_block();
-            assert_eq!(2, magink.get_remaining());
-            advance_block();
-            assert_eq!(1, magink.get_remaining());
-            advance_block();
-            assert_eq!(0, magink.get_remaining());
+        #[ink::test]
+        fn set_wizard_contract_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            assert_eq!(AccountId::from([0x01; 32]), magink.get_wizard_contract());
 
-            assert_eq!(1, magink.get_badges());
+            assert_eq!(
+                Ok(()),
+                magink.set_wizard_contract(AccountId::from([0x02; 32]))
+            );
+            assert_eq!(AccountId::from([0x02; 32]), magink.get_wizard_contract());
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                magink.set_wizard_contract(AccountId::from([0x03; 32])),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+            assert_eq!(AccountId::from([0x02; 32]), magink.get_wizard_contract());
+        }
+
+        #[ink::test]
+        fn transfer_ownership_works() {
+            let accounts = default_accounts();
+            let mut magink = init(AccountId::from([0x01; 32]), 1);
+            assert_eq!(Ownable::owner(&magink), Some(accounts.alice));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                Ownable::transfer_ownership(&mut magink, accounts.bob),
+                Err(OwnableError::CallerIsNotOwner)
+            );
+
+            set_sender(accounts.alice);
+            assert!(Ownable::transfer_ownership(&mut magink, accounts.bob).is_ok());
+            assert_eq!(Ownable::owner(&magink), Some(accounts.bob));
 
-            // panics due to off-chain environment does not support contract invocation
-            let result = std::panic::catch_unwind(move || magink.mint_wizard());
+            // the previous owner lost the access
+            assert_eq!(
+                magink.retire_lesson(0),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(Ok(()), magink.retire_lesson(0));
+        }
 
-            assert!(result.is_err());
+        type Event = <Magink as ink::reflect::ContractEventBase>::Type;
+
+        fn recorded_events() -> Vec<Event> {
+            ink::env::test::recorded_events()
+                .map(|event| {
+                    <Event as scale::Decode>::decode(&mut &event.data[..])
+                        .expect("invalid event data")
+                })
+                .collect()
         }
 
         fn default_accounts(
@@ -434,6 +4104,62 @@ pub mod magink {
             ink::env::test::default_accounts::<Environment>()
         }
 
+        /// Creates a Magink with `lessons` lessons and an era of one block, without
+        /// recording events.
+        fn init(wizard: AccountId, lessons: u8) -> Magink {
+            let mut magink = Magink::new(wizard);
+            assert_eq!(Ok(()), magink.set_era_length(1));
+            for lesson in 0..lessons {
+                assert!(magink
+                    .insert_lesson(Hash::from([lesson; 32]), format!("Lesson {}", lesson))
+                    .is_ok());
+            }
+            magink
+        }
+
+        /// Secret salt the learners commit their answers with.
+        const SECRET: [u8; 32] = [0x42; 32];
+
+        /// Creates a Magink with `lessons` lessons, the first one has a quiz
+        /// answered with "ink!".
+        fn init_with_quiz(lessons: u8) -> Magink {
+            let mut magink = init(AccountId::from([0x01; 32]), lessons);
+            let salt = Hash::from([0x07; 32]);
+            assert_eq!(Ok(()), magink.set_quiz(0, salt, answer_hash(&salt, "ink!")));
+            magink
+        }
+
+        /// Commits the caller to `answer` for the first lesson.
+        fn commit(magink: &mut Magink, answer: &str) -> Result<(), Error> {
+            let caller = ink::env::caller::<Environment>();
+            magink.commit_answer(0, commitment_hash(answer, &Hash::from(SECRET), &caller))
+        }
+
+        /// Reveals `answer` for the first lesson.
+        fn reveal(magink: &mut Magink, answer: &str) -> Result<bool, Error> {
+            magink.reveal_answer(0, String::from(answer), Hash::from(SECRET))
+        }
+
+        fn lesson_ids(magink: &Magink) -> Vec<LessonId> {
+            magink
+                .get_lessons()
+                .iter()
+                .map(|lesson| lesson.id)
+                .collect()
+        }
+
+        /// Claims `n` badges, the caller must have started with an era of one block.
+        fn claim_badges(magink: &mut Magink, n: u8) {
+            for _ in 0..n {
+                advance_block();
+                assert_eq!(Ok(()), magink.claim());
+            }
+        }
+
+        fn set_sender(sender: AccountId) {
+            ink::env::test::set_caller::<Environment>(sender);
+        }
+
         fn advance_n_blocks(n: u32) {
             for _ in 0..n {
                 advance_block();
@@ -0,0 +1,612 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::school_factory::SchoolFactoryRef;
+
+#[openbrush::implementation(Ownable)]
+#[openbrush::contract]
+pub mod school_factory {
+    use ink::{
+        codegen::TraitCallBuilder,
+        prelude::{
+            string::String,
+            vec::Vec,
+        },
+        storage::Mapping,
+        ToAccountId,
+    };
+
+    use openbrush::{
+        contracts::ownable::{
+            ownable_external,
+            OwnableError,
+        },
+        traits::Storage,
+    };
+
+    use magink::MaginkRef;
+
+    /// Errors returned by every fallible factory message.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Instantiating the Magink and Wizard pair failed.
+        InstantiationFailed,
+        /// A call to the freshly deployed Magink failed.
+        CrossContractCallFailed,
+        /// The registry ran out of school ids.
+        TooManySchools,
+        Ownable(OwnableError),
+    }
+
+    impl From<OwnableError> for Error {
+        fn from(error: OwnableError) -> Self {
+            Error::Ownable(error)
+        }
+    }
+
+    /// Maps the outcome of a call to a deployed Magink to a factory error.
+    fn magink_call_result<T>(
+        result: Result<ink::MessageResult<T>, ink::env::Error>,
+    ) -> Result<T, Error> {
+        match result {
+            Ok(Ok(value)) => Ok(value),
+            _ => Err(Error::CrossContractCallFailed),
+        }
+    }
+
+    pub type SchoolId = u32;
+
+    /// Largest page returned by the listing queries.
+    pub const MAX_PAGE_SIZE: u32 = 50;
+
+    #[ink(storage)]
+    #[derive(Storage)]
+    pub struct SchoolFactory {
+        #[storage_field]
+        ownable: ownable::Data,
+        magink_code_hash: Hash,
+        wizard_code_hash: Hash,
+        schools: Mapping<SchoolId, School>,
+        school_count: SchoolId,
+        owner_schools: Mapping<(AccountId, SchoolId), SchoolId>,
+        owner_school_count: Mapping<AccountId, SchoolId>,
+    }
+
+    /// Settings of the Magink and Wizard pair deployed for a school.
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SchoolParams {
+        // max supply of the wizard collection
+        pub max_supply: u64,
+
+        // wizard collection metadata
+        pub name: String,
+        pub symbol: String,
+        pub base_uri: String,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct School {
+        // id of the school in the registry
+        pub id: SchoolId,
+
+        // magink instance of the school
+        pub magink: AccountId,
+
+        // wizard collection, the owner holds its admin roles and magink only `MINTER`
+        pub wizard: AccountId,
+
+        // account that created the school and owns the magink instance
+        pub owner: AccountId,
+
+        // block number of the creation
+        pub created_at: BlockNumber,
+    }
+
+    /// Emitted when a school is created.
+    #[ink(event)]
+    pub struct SchoolCreated {
+        #[ink(topic)]
+        id: SchoolId,
+        #[ink(topic)]
+        owner: AccountId,
+        magink: AccountId,
+        wizard: AccountId,
+    }
+
+    impl SchoolFactory {
+        #[ink(constructor)]
+        pub fn new(magink_code_hash: Hash, wizard_code_hash: Hash) -> Self {
+            let mut instance = Self {
+                ownable: Default::default(),
+                magink_code_hash,
+                wizard_code_hash,
+                schools: Mapping::new(),
+                school_count: 0,
+                owner_schools: Mapping::new(),
+                owner_school_count: Mapping::new(),
+            };
+
+            ownable::Internal::_init_with_owner(&mut instance, Self::env().caller());
+
+            instance
+        }
+
+        /// Deploys a Magink instance together with its own Wizard collection.
+        ///
+        /// The new Magink mints on the Wizard, the caller administers the Wizard and
+        /// gets the Magink handed over.
+        #[ink(message)]
+        pub fn create_school(&mut self, params: SchoolParams) -> Result<SchoolId, Error> {
+            let caller = self.env().caller();
+            let id = self.school_count;
+
+            let mut magink = match MaginkRef::new_with_wizard(
+                self.wizard_code_hash,
+                params.max_supply,
+                params.name,
+                params.symbol,
+                params.base_uri,
+                caller,
+            )
+            .code_hash(self.magink_code_hash)
+            .endowment(0)
+            .salt_bytes(id.to_le_bytes())
+            .try_instantiate()
+            {
+                Ok(Ok(magink)) => magink,
+                _ => return Err(Error::InstantiationFailed),
+            };
+
+            let wizard =
+                magink_call_result(magink.call().get_wizard_contract().try_invoke())?;
+
+            magink_call_result(
+                ownable_external::Ownable::transfer_ownership(magink.call_mut(), caller)
+                    .try_invoke(),
+            )??;
+
+            self.register(caller, magink.to_account_id(), wizard)
+        }
+
+        /// Returns the school with the given id.
+        #[ink(message)]
+        pub fn get_school(&self, id: SchoolId) -> Option<School> {
+            self.schools.get(id)
+        }
+
+        /// Returns the number of schools created.
+        #[ink(message)]
+        pub fn school_count(&self) -> SchoolId {
+            self.school_count
+        }
+
+        /// Lists up to `limit` schools starting at the id `from`.
+        ///
+        /// The page is capped at `MAX_PAGE_SIZE` schools.
+        #[ink(message)]
+        pub fn get_schools(&self, from: SchoolId, limit: u32) -> Vec<School> {
+            let end = from
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.school_count);
+
+            (from..end).filter_map(|id| self.schools.get(id)).collect()
+        }
+
+        /// Returns the number of schools created by `owner`.
+        #[ink(message)]
+        pub fn school_count_of(&self, owner: AccountId) -> SchoolId {
+            self.owner_school_count.get(owner).unwrap_or(0)
+        }
+
+        /// Lists up to `limit` schools of `owner`, skipping the first `from` ones.
+        ///
+        /// The page is capped at `MAX_PAGE_SIZE` schools.
+        #[ink(message)]
+        pub fn get_schools_of(
+            &self,
+            owner: AccountId,
+            from: SchoolId,
+            limit: u32,
+        ) -> Vec<School> {
+            let end = from
+                .saturating_add(limit.min(MAX_PAGE_SIZE))
+                .min(self.school_count_of(owner));
+
+            (from..end)
+                .filter_map(|index| self.owner_schools.get((owner, index)))
+                .filter_map(|id| self.schools.get(id))
+                .collect()
+        }
+
+        /// Sets the code hashes used for new schools.
+        #[ink(message)]
+        #[openbrush::modifiers(only_owner)]
+        pub fn set_code_hashes(
+            &mut self,
+            magink_code_hash: Hash,
+            wizard_code_hash: Hash,
+        ) -> Result<(), Error> {
+            self.magink_code_hash = magink_code_hash;
+            self.wizard_code_hash = wizard_code_hash;
+            Ok(())
+        }
+
+        /// Returns the Magink and Wizard code hashes used for new schools.
+        #[ink(message)]
+        pub fn get_code_hashes(&self) -> (Hash, Hash) {
+            (self.magink_code_hash, self.wizard_code_hash)
+        }
+
+        /// Records a deployed pair in the registry.
+        fn register(
+            &mut self,
+            owner: AccountId,
+            magink: AccountId,
+            wizard: AccountId,
+        ) -> Result<SchoolId, Error> {
+            let id = self.school_count;
+            self.school_count = id.checked_add(1).ok_or(Error::TooManySchools)?;
+
+            let school = School {
+                id,
+                magink,
+                wizard,
+                owner,
+                created_at: self.env().block_number(),
+            };
+            self.schools.insert(id, &school);
+
+            let owned = self.school_count_of(owner);
+            self.owner_schools.insert((owner, owned), &id);
+            self.owner_school_count.insert(owner, &(owned + 1));
+
+            self.env().emit_event(SchoolCreated {
+                id,
+                owner,
+                magink,
+                wizard,
+            });
+
+            Ok(id)
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use magink::address_of;
+        use wizard::{
+            WizardRef,
+            MINTER,
+        };
+
+        use ink_e2e::{
+            build_message,
+            PolkadotConfig,
+        };
+
+        use openbrush::contracts::{
+            access_control::{
+                access_control_external::AccessControl,
+                DEFAULT_ADMIN_ROLE,
+            },
+            ownable::ownable_external::Ownable,
+        };
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn e2e_create_school_works(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let magink_code_hash = client
+                .upload("magink", &ink_e2e::alice(), None)
+                .await
+                .expect("uploading magink failed")
+                .code_hash;
+
+            let wizard_code_hash = client
+                .upload("wizard", &ink_e2e::alice(), None)
+                .await
+                .expect("uploading wizard failed")
+                .code_hash;
+
+            let constructor = SchoolFactoryRef::new(magink_code_hash, wizard_code_hash);
+
+            let factory_account_id = client
+                .instantiate("school_factory", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("factory contract instantiate failed")
+                .account_id;
+
+            // bob creates a school
+            let params = SchoolParams {
+                max_supply: 10,
+                name: String::from("Sorcerer"),
+                symbol: String::from("SRC"),
+                base_uri: String::from("ipfs://sorcerer/"),
+            };
+
+            let create_school =
+                build_message::<SchoolFactoryRef>(factory_account_id.clone())
+                    .call(|factory| factory.create_school(params.clone()));
+
+            let school_id = client
+                .call(&ink_e2e::bob(), create_school, 0, None)
+                .await
+                .expect("calling create_school failed")
+                .return_value();
+
+            assert_eq!(school_id, Ok(0));
+
+            let school = {
+                let msg = build_message::<SchoolFactoryRef>(factory_account_id.clone())
+                    .call(|factory| factory.get_school(0));
+
+                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            }
+            .return_value()
+            .expect("school not registered");
+
+            assert_eq!(school.owner, address_of!(bob));
+
+            // bob owns the magink instance
+            let magink_owner = {
+                let msg = build_message::<MaginkRef>(school.magink.clone())
+                    .call(|magink| magink.owner());
+
+                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(magink_owner, Some(address_of!(bob)));
+
+            // the magink instance mints on its wizard collection
+            let is_minter = {
+                let msg = build_message::<WizardRef>(school.wizard.clone())
+                    .call(|wizard| wizard.has_role(MINTER, Some(school.magink)));
+
+                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert!(is_minter);
+
+            // and bob administers the wizard collection
+            let is_admin = {
+                let msg =
+                    build_message::<WizardRef>(school.wizard.clone()).call(|wizard| {
+                        wizard.has_role(DEFAULT_ADMIN_ROLE, Some(address_of!(bob)))
+                    });
+
+                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert!(is_admin);
+
+            // a second school with the same params gets its own pair
+            let create_school =
+                build_message::<SchoolFactoryRef>(factory_account_id.clone())
+                    .call(|factory| factory.create_school(params.clone()));
+
+            client
+                .call(&ink_e2e::bob(), create_school, 0, None)
+                .await
+                .expect("calling create_school failed");
+
+            let schools = {
+                let msg = build_message::<SchoolFactoryRef>(factory_account_id.clone())
+                    .call(|factory| factory.get_schools_of(address_of!(bob), 0, 10));
+
+                client.call_dry_run(&ink_e2e::bob(), &msg, 0, None).await
+            }
+            .return_value();
+
+            assert_eq!(schools.len(), 2);
+            assert_ne!(schools[0].magink, schools[1].magink);
+            assert_ne!(schools[0].wizard, schools[1].wizard);
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[ink::test]
+        fn new_works() {
+            let accounts = default_accounts();
+            let factory = init();
+
+            assert_eq!(
+                factory.get_code_hashes(),
+                (magink_code_hash(), wizard_code_hash())
+            );
+            assert_eq!(factory.school_count(), 0);
+            assert_eq!(factory.get_school(0), None);
+            assert!(factory.get_schools(0, 10).is_empty());
+            assert_eq!(Ownable::owner(&factory), Some(accounts.alice));
+        }
+
+        #[ink::test]
+        fn register_works() {
+            let accounts = default_accounts();
+            let mut factory = init();
+
+            assert_eq!(
+                Ok(0),
+                factory.register(
+                    accounts.bob,
+                    AccountId::from([0x10; 32]),
+                    AccountId::from([0x20; 32])
+                )
+            );
+
+            let school = School {
+                id: 0,
+                magink: AccountId::from([0x10; 32]),
+                wizard: AccountId::from([0x20; 32]),
+                owner: accounts.bob,
+                created_at: 0,
+            };
+            assert_eq!(factory.get_school(0), Some(school.clone()));
+            assert_eq!(factory.school_count(), 1);
+            assert_eq!(factory.school_count_of(accounts.bob), 1);
+            assert_eq!(factory.get_schools_of(accounts.bob, 0, 10), vec![school]);
+
+            // ids are never reused, the registry is full at the last one
+            factory.school_count = SchoolId::MAX;
+            assert_eq!(
+                Err(Error::TooManySchools),
+                factory.register(
+                    accounts.bob,
+                    AccountId::from([0x11; 32]),
+                    AccountId::from([0x21; 32])
+                )
+            );
+            assert_eq!(factory.school_count_of(accounts.bob), 1);
+
+            let events = recorded_events();
+            assert_eq!(events.len(), 1);
+            match &events[0] {
+                Event::SchoolCreated(event) => {
+                    assert_eq!(event.id, 0);
+                    assert_eq!(event.owner, accounts.bob);
+                    assert_eq!(event.magink, AccountId::from([0x10; 32]));
+                    assert_eq!(event.wizard, AccountId::from([0x20; 32]));
+                }
+            }
+        }
+
+        #[ink::test]
+        fn get_schools_is_paged() {
+            let accounts = default_accounts();
+            let mut factory = init();
+            register_schools(&mut factory, accounts.bob, 5);
+
+            let ids = |schools: Vec<School>| -> Vec<SchoolId> {
+                schools.iter().map(|school| school.id).collect()
+            };
+
+            assert_eq!(ids(factory.get_schools(0, 2)), vec![0, 1]);
+            assert_eq!(ids(factory.get_schools(2, 2)), vec![2, 3]);
+            assert_eq!(ids(factory.get_schools(4, 2)), vec![4]);
+            assert!(factory.get_schools(5, 2).is_empty());
+            assert!(factory.get_schools(0, 0).is_empty());
+            assert!(factory.get_schools(u32::MAX, u32::MAX).is_empty());
+        }
+
+        #[ink::test]
+        fn get_schools_is_capped() {
+            let accounts = default_accounts();
+            let mut factory = init();
+            register_schools(&mut factory, accounts.bob, MAX_PAGE_SIZE + 1);
+
+            assert_eq!(
+                factory.get_schools(0, u32::MAX).len(),
+                MAX_PAGE_SIZE as usize
+            );
+            assert_eq!(factory.get_schools(MAX_PAGE_SIZE, u32::MAX).len(), 1);
+        }
+
+        #[ink::test]
+        fn get_schools_of_is_paged() {
+            let accounts = default_accounts();
+            let mut factory = init();
+            register_schools(&mut factory, accounts.bob, 2);
+            register_schools(&mut factory, accounts.charlie, 1);
+            register_schools(&mut factory, accounts.bob, 1);
+
+            let ids = |schools: Vec<School>| -> Vec<SchoolId> {
+                schools.iter().map(|school| school.id).collect()
+            };
+
+            assert_eq!(factory.school_count_of(accounts.bob), 3);
+            assert_eq!(
+                ids(factory.get_schools_of(accounts.bob, 0, 10)),
+                vec![0, 1, 3]
+            );
+            assert_eq!(ids(factory.get_schools_of(accounts.bob, 1, 1)), vec![1]);
+            assert_eq!(
+                ids(factory.get_schools_of(accounts.charlie, 0, 10)),
+                vec![2]
+            );
+            assert!(factory.get_schools_of(accounts.django, 0, 10).is_empty());
+        }
+
+        #[ink::test]
+        fn set_code_hashes_works() {
+            let accounts = default_accounts();
+            let mut factory = init();
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                factory.set_code_hashes(Hash::from([0x03; 32]), Hash::from([0x04; 32])),
+                Err(Error::Ownable(OwnableError::CallerIsNotOwner))
+            );
+
+            set_sender(accounts.alice);
+            assert_eq!(
+                Ok(()),
+                factory.set_code_hashes(Hash::from([0x03; 32]), Hash::from([0x04; 32]))
+            );
+            assert_eq!(
+                factory.get_code_hashes(),
+                (Hash::from([0x03; 32]), Hash::from([0x04; 32]))
+            );
+        }
+
+        type Event = <SchoolFactory as ink::reflect::ContractEventBase>::Type;
+
+        fn recorded_events() -> Vec<Event> {
+            ink::env::test::recorded_events()
+                .map(|event| {
+                    <Event as scale::Decode>::decode(&mut &event.data[..])
+                        .expect("invalid event data")
+                })
+                .collect()
+        }
+
+        fn magink_code_hash() -> Hash {
+            Hash::from([0x01; 32])
+        }
+
+        fn wizard_code_hash() -> Hash {
+            Hash::from([0x02; 32])
+        }
+
+        fn init() -> SchoolFactory {
+            SchoolFactory::new(magink_code_hash(), wizard_code_hash())
+        }
+
+        /// Registers `n` schools of `owner` with distinct contract addresses.
+        fn register_schools(factory: &mut SchoolFactory, owner: AccountId, n: u32) {
+            for _ in 0..n {
+                let id = factory.school_count() as u8;
+                assert!(factory
+                    .register(
+                        owner,
+                        AccountId::from([id; 32]),
+                        AccountId::from([!id; 32])
+                    )
+                    .is_ok());
+            }
+        }
+
+        fn default_accounts(
+        ) -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+            ink::env::test::default_accounts::<Environment>()
+        }
+
+        fn set_sender(sender: AccountId) {
+            ink::env::test::set_caller::<Environment>(sender);
+        }
+    }
+}
@@ -1,10 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
-pub use self::wizard::WizardRef;
+pub use self::wizard::{
+    WizardRef,
+    METADATA_ADMIN,
+    MINTER,
+};
 
 #[openbrush::implementation(
     PSP34,
-    Ownable,
+    AccessControl,
+    Pausable,
     PSP34Mintable,
     PSP34Enumerable,
     PSP34Metadata
@@ -22,7 +27,16 @@ pub mod wizard {
         Env,
     };
 
-    use openbrush::traits::Storage;
+    use openbrush::{
+        contracts::{
+            access_control::{
+                AccessControlError,
+                DEFAULT_ADMIN_ROLE,
+            },
+            pausable::PausableError,
+        },
+        traits::Storage,
+    };
 
     #[ink(storage)]
     #[derive(Default, Storage)]
@@ -31,7 +45,10 @@ pub mod wizard {
         psp34: psp34::Data,
 
         #[storage_field]
-        ownable: ownable::Data,
+        access: access_control::Data,
+
+        #[storage_field]
+        pausable: pausable::Data,
 
         #[storage_field]
         metadata: metadata::Data,
@@ -42,6 +59,34 @@ pub mod wizard {
         max_supply: u64,
     }
 
+    /// Role allowed to mint tokens.
+    pub const MINTER: RoleType = ink::selector_id!("MINTER");
+
+    /// Role allowed to change the collection metadata.
+    pub const METADATA_ADMIN: RoleType = ink::selector_id!("METADATA_ADMIN");
+
+    /// Errors returned by `pause` and `unpause`.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PauseError {
+        /// The caller is not the default admin.
+        AccessControl(AccessControlError),
+        /// The collection is paused, or not paused when unpausing.
+        Pausable(PausableError),
+    }
+
+    impl From<AccessControlError> for PauseError {
+        fn from(error: AccessControlError) -> Self {
+            PauseError::AccessControl(error)
+        }
+    }
+
+    impl From<PausableError> for PauseError {
+        fn from(error: PausableError) -> Self {
+            PauseError::Pausable(error)
+        }
+    }
+
     #[ink(event)]
     pub struct Transfer {
         #[ink(topic)]
@@ -54,6 +99,20 @@ pub mod wizard {
         id: Id,
     }
 
+    /// Emitted when the admin pauses minting and transfers.
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    /// Emitted when the admin unpauses minting and transfers.
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        account: AccountId,
+    }
+
     #[overrider(psp34::Internal)]
     fn _emit_transfer_event(
         &self,
@@ -64,8 +123,30 @@ pub mod wizard {
         self.env().emit_event(Transfer { from, to, id });
     }
 
+    #[overrider(pausable::Internal)]
+    fn _emit_paused_event(&self, account: AccountId) {
+        self.env().emit_event(Paused { account });
+    }
+
+    #[overrider(pausable::Internal)]
+    fn _emit_unpaused_event(&self, account: AccountId) {
+        self.env().emit_event(Unpaused { account });
+    }
+
+    // every mint and transfer goes through here
+    #[overrider(psp34::Internal)]
+    #[openbrush::modifiers(when_not_paused)]
+    fn _before_token_transfer(
+        &mut self,
+        _from: Option<&AccountId>,
+        _to: Option<&AccountId>,
+        _id: &Id,
+    ) -> Result<(), PSP34Error> {
+        Ok(())
+    }
+
     #[overrider(PSP34Mintable)]
-    #[openbrush::modifiers(only_owner)]
+    #[openbrush::modifiers(only_role(MINTER), when_not_paused)]
     fn mint(&mut self, account: AccountId, id: Id) -> Result<(), PSP34Error> {
         if PSP34Impl::total_supply(self) as u64 >= self.max_supply {
             return Err(PSP34Error::Custom(String::from("CollectionFull")))
@@ -77,9 +158,37 @@ pub mod wizard {
     impl Wizard {
         #[ink(constructor)]
         pub fn new(max_supply: u64) -> Self {
+            Self::new_with_metadata(
+                max_supply,
+                String::from("Wizard34"),
+                String::from("WZ34"),
+                String::from("https://bafybeibwbgwzqigw7touxmixxvkd3wfcf2rcljgbt75na7rwwnw4ojgljy.ipfs.nftstorage.link/"),
+                Self::env().caller(),
+            )
+        }
+
+        /// Creates a collection with the given name, symbol and base uri.
+        ///
+        /// `admin` becomes the default admin and gets the `METADATA_ADMIN` role, the
+        /// caller only gets the `MINTER` role.
+        #[ink(constructor)]
+        pub fn new_with_metadata(
+            max_supply: u64,
+            name: String,
+            symbol: String,
+            base_uri: String,
+            admin: AccountId,
+        ) -> Self {
             let mut _instance = Self::default();
 
-            ownable::Internal::_init_with_owner(&mut _instance, Self::env().caller());
+            let caller = Self::env().caller();
+            access_control::Internal::_init_with_admin(&mut _instance, Some(admin));
+            access_control::Internal::_setup_role(&mut _instance, MINTER, Some(caller));
+            access_control::Internal::_setup_role(
+                &mut _instance,
+                METADATA_ADMIN,
+                Some(admin),
+            );
 
             let collection_id = psp34::PSP34Impl::collection_id(&_instance);
 
@@ -87,21 +196,21 @@ pub mod wizard {
                 &mut _instance,
                 collection_id.clone(),
                 String::from("name"),
-                String::from("Wizard34"),
+                name,
             );
 
             metadata::Internal::_set_attribute(
                 &mut _instance,
                 collection_id.clone(),
                 String::from("symbol"),
-                String::from("WZ34"),
+                symbol,
             );
 
             metadata::Internal::_set_attribute(
                 &mut _instance,
                 collection_id,
                 String::from("baseUri"),
-                String::from("https://bafybeibwbgwzqigw7touxmixxvkd3wfcf2rcljgbt75na7rwwnw4ojgljy.ipfs.nftstorage.link/"),
+                base_uri,
             );
 
             _instance.max_supply = max_supply;
@@ -109,6 +218,20 @@ pub mod wizard {
             _instance
         }
 
+        /// Pauses minting and transfers.
+        #[ink(message)]
+        #[openbrush::modifiers(only_role(DEFAULT_ADMIN_ROLE))]
+        pub fn pause(&mut self) -> Result<(), PauseError> {
+            Ok(pausable::Internal::_pause(self)?)
+        }
+
+        /// Lifts a pause set with `pause`.
+        #[ink(message)]
+        #[openbrush::modifiers(only_role(DEFAULT_ADMIN_ROLE))]
+        pub fn unpause(&mut self) -> Result<(), PauseError> {
+            Ok(pausable::Internal::_unpause(self)?)
+        }
+
         #[ink(message)]
         pub fn max_supply(&self) -> u64 {
             self.max_supply
@@ -119,8 +242,24 @@ pub mod wizard {
             PSP34Impl::total_supply(self)
         }
 
+        /// Mints the next free token id to `to` and returns it.
+        ///
+        /// Ids start right after the current supply and skip the ones already
+        /// minted, so direct mints by other minters never collide with it.
         #[ink(message)]
-        #[openbrush::modifiers(only_owner)]
+        #[openbrush::modifiers(only_role(MINTER))]
+        pub fn mint_next(&mut self, to: AccountId) -> Result<Id, PSP34Error> {
+            let mut next = PSP34Impl::total_supply(self) as u64 + 1;
+            while psp34::InternalImpl::_owner_of(self, &Id::U64(next)).is_some() {
+                next += 1;
+            }
+
+            PSP34Mintable::mint(self, to, Id::U64(next))?;
+            Ok(Id::U64(next))
+        }
+
+        #[ink(message)]
+        #[openbrush::modifiers(only_role(METADATA_ADMIN))]
         pub fn set_base_uri(&mut self, uri: String) -> Result<(), PSP34Error> {
             let id = PSP34Impl::collection_id(self);
             metadata::Internal::_set_attribute(self, id, String::from("baseUri"), uri);
@@ -129,7 +268,7 @@ pub mod wizard {
         }
 
         #[ink(message)]
-        #[openbrush::modifiers(only_owner)]
+        #[openbrush::modifiers(only_role(METADATA_ADMIN))]
         pub fn set_name(&mut self, name: String) -> Result<(), PSP34Error> {
             let id = PSP34Impl::collection_id(self);
             metadata::Internal::_set_attribute(self, id, String::from("name"), name);
@@ -138,7 +277,7 @@ pub mod wizard {
         }
 
         #[ink(message)]
-        #[openbrush::modifiers(only_owner)]
+        #[openbrush::modifiers(only_role(METADATA_ADMIN))]
         pub fn set_symbol(&mut self, symbol: String) -> Result<(), PSP34Error> {
             let id = PSP34Impl::collection_id(self);
             metadata::Internal::_set_attribute(self, id, String::from("symbol"), symbol);
@@ -211,7 +350,10 @@ pub mod wizard {
             prelude::string::String,
         };
 
-        use openbrush::contracts::psp34::*;
+        use openbrush::contracts::{
+            access_control::*,
+            psp34::*,
+        };
 
         const MAX_SUPPLY: u64 = 3;
         const BASE_URI: &str = "https://bafybeibwbgwzqigw7touxmixxvkd3wfcf2rcljgbt75na7rwwnw4ojgljy.ipfs.nftstorage.link/";
@@ -264,13 +406,70 @@ pub mod wizard {
             assert_eq!(wizard.get_total_supply(), 0);
         }
 
+        #[ink::test]
+        fn new_with_metadata_works() {
+            let accounts = default_accounts();
+            let wizard = Wizard::new_with_metadata(
+                5,
+                String::from("Sorcerer"),
+                String::from("SRC"),
+                String::from("ipfs://sorcerer/"),
+                accounts.bob,
+            );
+            let collection_id = PSP34Impl::collection_id(&wizard);
+
+            assert_eq!(
+                metadata::PSP34MetadataImpl::get_attribute(
+                    &wizard,
+                    collection_id.clone(),
+                    String::from("name")
+                ),
+                Some(String::from("Sorcerer"))
+            );
+
+            assert_eq!(
+                metadata::PSP34MetadataImpl::get_attribute(
+                    &wizard,
+                    collection_id.clone(),
+                    String::from("symbol")
+                ),
+                Some(String::from("SRC"))
+            );
+
+            assert_eq!(
+                metadata::PSP34MetadataImpl::get_attribute(
+                    &wizard,
+                    collection_id,
+                    String::from("baseUri")
+                ),
+                Some(String::from("ipfs://sorcerer/"))
+            );
+
+            assert_eq!(wizard.max_supply(), 5);
+
+            // the caller only mints, the admin manages the collection
+            let alice = Some(accounts.alice);
+            assert!(AccessControl::has_role(&wizard, MINTER, alice));
+            assert!(!AccessControl::has_role(&wizard, DEFAULT_ADMIN_ROLE, alice));
+            assert!(!AccessControl::has_role(&wizard, METADATA_ADMIN, alice));
+
+            let bob = Some(accounts.bob);
+            assert!(AccessControl::has_role(&wizard, DEFAULT_ADMIN_ROLE, bob));
+            assert!(AccessControl::has_role(&wizard, METADATA_ADMIN, bob));
+            assert!(!AccessControl::has_role(&wizard, MINTER, bob));
+        }
+
         #[ink::test]
         fn mint_works() {
             let mut wizard = init();
             assert_eq!(wizard.get_total_supply(), 0);
 
             let accounts = default_accounts();
-            assert_eq!(Ownable::owner(&wizard).unwrap(), accounts.alice);
+            assert!(AccessControl::has_role(
+                &wizard,
+                MINTER,
+                Some(accounts.alice)
+            ));
 
             assert!(PSP34Mintable::mint(&mut wizard, accounts.bob, Id::U64(1)).is_ok());
             assert_eq!(wizard.get_total_supply(), 1);
@@ -309,15 +508,247 @@ pub mod wizard {
         }
 
         #[ink::test]
-        fn transfer_ownership_works() {
+        fn mint_next_works() {
             let mut wizard = init();
             let accounts = default_accounts();
 
-            assert_eq!(Ownable::owner(&wizard).unwrap(), accounts.alice);
-            assert!(Ownable::transfer_ownership(&mut wizard, accounts.bob).is_ok());
+            assert_eq!(wizard.mint_next(accounts.bob), Ok(Id::U64(1)));
+            assert_eq!(wizard.mint_next(accounts.eve), Ok(Id::U64(2)));
+
+            assert_eq!(PSP34Impl::owner_of(&wizard, Id::U64(1)), Some(accounts.bob));
+            assert_eq!(PSP34Impl::owner_of(&wizard, Id::U64(2)), Some(accounts.eve));
+            assert_eq!(wizard.get_total_supply(), 2);
+        }
+
+        #[ink::test]
+        fn mint_next_skips_taken_ids() {
+            let mut wizard = Wizard::new(10);
+            let accounts = default_accounts();
+
+            assert!(PSP34Mintable::mint(&mut wizard, accounts.bob, Id::U64(1)).is_ok());
+            assert!(PSP34Mintable::mint(&mut wizard, accounts.bob, Id::U64(3)).is_ok());
 
-            assert_ne!(Ownable::owner(&wizard).unwrap(), accounts.alice);
-            assert_eq!(Ownable::owner(&wizard).unwrap(), accounts.bob);
+            assert_eq!(wizard.mint_next(accounts.eve), Ok(Id::U64(4)));
+            assert_eq!(wizard.mint_next(accounts.eve), Ok(Id::U64(5)));
+            assert_eq!(PSP34Impl::owner_of(&wizard, Id::U64(2)), None);
+        }
+
+        #[ink::test]
+        fn mint_next_checks_minter_and_max_supply() {
+            let mut wizard = init();
+            let accounts = default_accounts();
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                wizard.mint_next(accounts.bob),
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
+            );
+
+            set_sender(accounts.alice);
+            for _ in 0..MAX_SUPPLY {
+                assert!(wizard.mint_next(accounts.bob).is_ok());
+            }
+
+            assert_eq!(
+                wizard.mint_next(accounts.bob),
+                Err(PSP34Error::Custom(String::from("CollectionFull")))
+            );
+        }
+
+        #[ink::test]
+        fn mint_requires_minter_role() {
+            let mut wizard = init();
+            let accounts = default_accounts();
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                PSP34Mintable::mint(&mut wizard, accounts.bob, Id::U64(1)),
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
+            );
+
+            set_sender(accounts.alice);
+            assert!(
+                AccessControl::grant_role(&mut wizard, MINTER, Some(accounts.bob))
+                    .is_ok()
+            );
+
+            set_sender(accounts.bob);
+            assert!(PSP34Mintable::mint(&mut wizard, accounts.bob, Id::U64(1)).is_ok());
+            assert_eq!(wizard.mint_next(accounts.eve), Ok(Id::U64(2)));
+
+            set_sender(accounts.alice);
+            assert!(
+                AccessControl::revoke_role(&mut wizard, MINTER, Some(accounts.bob))
+                    .is_ok()
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                wizard.mint_next(accounts.eve),
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
+            );
+        }
+
+        #[ink::test]
+        fn several_minters_share_the_collection() {
+            let mut wizard = Wizard::new(10);
+            let accounts = default_accounts();
+
+            assert!(
+                AccessControl::grant_role(&mut wizard, MINTER, Some(accounts.bob))
+                    .is_ok()
+            );
+            assert!(AccessControl::grant_role(
+                &mut wizard,
+                MINTER,
+                Some(accounts.charlie)
+            )
+            .is_ok());
+
+            set_sender(accounts.bob);
+            assert_eq!(wizard.mint_next(accounts.eve), Ok(Id::U64(1)));
+
+            set_sender(accounts.charlie);
+            assert_eq!(wizard.mint_next(accounts.eve), Ok(Id::U64(2)));
+
+            // minting does not grant metadata rights
+            assert_eq!(
+                wizard.set_name(String::from("Charlie34")),
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
+            );
+        }
+
+        #[ink::test]
+        fn metadata_admin_is_separate_from_minter() {
+            let mut wizard = init();
+            let accounts = default_accounts();
+
+            // alice keeps the metadata rights while bob only mints
+            assert!(
+                AccessControl::grant_role(&mut wizard, MINTER, Some(accounts.bob))
+                    .is_ok()
+            );
+            assert!(AccessControl::renounce_role(
+                &mut wizard,
+                MINTER,
+                Some(accounts.alice)
+            )
+            .is_ok());
+
+            assert_eq!(
+                PSP34Mintable::mint(&mut wizard, accounts.alice, Id::U64(1)),
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
+            );
+            assert!(wizard.set_symbol(String::from("WZ")).is_ok());
+
+            set_sender(accounts.bob);
+            assert!(PSP34Mintable::mint(&mut wizard, accounts.bob, Id::U64(1)).is_ok());
+
+            // only the default admin hands out roles
+            assert_eq!(
+                AccessControl::grant_role(
+                    &mut wizard,
+                    METADATA_ADMIN,
+                    Some(accounts.bob)
+                ),
+                Err(AccessControlError::MissingRole)
+            );
+        }
+
+        #[ink::test]
+        fn pause_works() {
+            let mut wizard = init();
+            let accounts = default_accounts();
+            assert!(!Pausable::paused(&wizard));
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                wizard.pause(),
+                Err(PauseError::AccessControl(AccessControlError::MissingRole))
+            );
+
+            set_sender(accounts.alice);
+            assert!(wizard.pause().is_ok());
+            assert!(Pausable::paused(&wizard));
+            assert_eq!(
+                wizard.pause(),
+                Err(PauseError::Pausable(PausableError::Paused))
+            );
+
+            set_sender(accounts.bob);
+            assert_eq!(
+                wizard.unpause(),
+                Err(PauseError::AccessControl(AccessControlError::MissingRole))
+            );
+
+            set_sender(accounts.alice);
+            assert!(wizard.unpause().is_ok());
+            assert!(!Pausable::paused(&wizard));
+            assert_eq!(
+                wizard.unpause(),
+                Err(PauseError::Pausable(PausableError::NotPaused))
+            );
+
+            type Event = <Wizard as ink::reflect::ContractEventBase>::Type;
+            let events: Vec<Event> = ink::env::test::recorded_events()
+                .map(|event| {
+                    <Event as scale::Decode>::decode(&mut &event.data[..])
+                        .expect("invalid event data")
+                })
+                .collect();
+
+            assert_eq!(events.len(), 2);
+            match (&events[0], &events[1]) {
+                (Event::Paused(paused), Event::Unpaused(unpaused)) => {
+                    assert_eq!(paused.account, accounts.alice);
+                    assert_eq!(unpaused.account, accounts.alice);
+                }
+                _ => panic!("expected Paused and Unpaused events"),
+            }
+        }
+
+        #[ink::test]
+        fn mint_fails_while_paused() {
+            let mut wizard = init();
+            let accounts = default_accounts();
+
+            assert!(wizard.pause().is_ok());
+            assert_eq!(
+                PSP34Mintable::mint(&mut wizard, accounts.bob, Id::U64(1)),
+                Err(PSP34Error::from(PausableError::Paused))
+            );
+            assert_eq!(
+                wizard.mint_next(accounts.bob),
+                Err(PSP34Error::from(PausableError::Paused))
+            );
+            assert_eq!(wizard.get_total_supply(), 0);
+
+            assert!(wizard.unpause().is_ok());
+            assert_eq!(wizard.mint_next(accounts.bob), Ok(Id::U64(1)));
+        }
+
+        #[ink::test]
+        fn transfer_fails_while_paused() {
+            let mut wizard = init();
+            let accounts = default_accounts();
+
+            assert!(PSP34Mintable::mint(&mut wizard, accounts.alice, Id::U64(1)).is_ok());
+
+            assert!(wizard.pause().is_ok());
+            assert_eq!(
+                PSP34::transfer(&mut wizard, accounts.bob, Id::U64(1), vec![]),
+                Err(PSP34Error::from(PausableError::Paused))
+            );
+            assert_eq!(
+                PSP34Impl::owner_of(&wizard, Id::U64(1)),
+                Some(accounts.alice)
+            );
+
+            assert!(wizard.unpause().is_ok());
+            assert!(
+                PSP34::transfer(&mut wizard, accounts.bob, Id::U64(1), vec![]).is_ok()
+            );
+            assert_eq!(PSP34Impl::owner_of(&wizard, Id::U64(1)), Some(accounts.bob));
         }
 
         #[ink::test]
@@ -379,7 +810,7 @@ pub mod wizard {
             set_sender(accounts.bob);
             assert_eq!(
                 wizard.set_base_uri(NEW_BASE_URI.into()),
-                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
             );
         }
 
@@ -407,7 +838,7 @@ pub mod wizard {
             set_sender(accounts.bob);
             assert_eq!(
                 wizard.set_name(NEW_NAME.into()),
-                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
             );
 
             set_sender(accounts.alice);
@@ -424,7 +855,7 @@ pub mod wizard {
             set_sender(accounts.bob);
             assert_eq!(
                 wizard.set_symbol(NEW_SYMBOL.into()),
-                Err(PSP34Error::Custom(String::from("O::CallerIsNotOwner")))
+                Err(PSP34Error::Custom(String::from("AC::MissingRole")))
             );
         }
     }
@@ -1,19 +1,14 @@
 import { useEffect, useRef } from "react";
-import { Values } from "../types";
 import { useMaginkContract } from "../hooks";
 import { pickTxInfo } from "useink/utils";
 
-interface Props {
-  values: Values;
-}
-
-export function DryRunResult({ values }: Props) {
+export function DryRunResult() {
   const { startDryRun } = useMaginkContract();
   const timeoutId = useRef<NodeJS.Timeout | null>(null);
 
   useEffect(() => {
     async function getOutcome() {
-      startDryRun?.send([values.blocksToLive], { defaultCaller: true });
+      startDryRun?.send([], { defaultCaller: true });
     }
 
     function debouncedDryRun() {
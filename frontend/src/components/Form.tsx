@@ -1,5 +1,5 @@
 import { Form, useFormikContext } from 'formik';
-import { Values } from '../types';
+import { Lesson, Values } from '../types';
 import { NewUserGuide } from './NewUserGuide';
 import { useMaginkContract, useUI } from '../hooks';
 import { pickDecodedError } from 'useink/utils';
@@ -12,11 +12,13 @@ interface Props {
   awake: () => void;
   isAwake: boolean;
   badges: number;
+  badgesRequired: number;
+  lessons: Lesson[];
   remainingBlocks: number;
   runtimeError?: any;
 }
 
-export const MaginkForm = ({ awake, isAwake, remainingBlocks, runtimeError, badges }: Props) => {
+export const MaginkForm = ({ awake, isAwake, remainingBlocks, runtimeError, badges, badgesRequired, lessons }: Props) => {
   const { isSubmitting, isValid } = useFormikContext<Values>();
   const { claimDryRun, mintDryRun, magink } = useMaginkContract();
   const { account } = useWallet();
@@ -33,7 +35,7 @@ export const MaginkForm = ({ awake, isAwake, remainingBlocks, runtimeError, badg
     <Form>
       {account && !isAwake && (
         <>
-        <p>Press Start for ten swanky lessons about ink! and Astar Network</p>
+        <p>Press Start for {lessons.length} swanky lessons about ink! and Astar Network</p>
         <br/>
         <Button type="button"
           disabled={isSubmitting || !isValid || !hasFunds } onClick={awake}>
@@ -47,7 +49,7 @@ export const MaginkForm = ({ awake, isAwake, remainingBlocks, runtimeError, badg
           <br />
           <Button
             type="button"
-            disabled={isSubmitting || !isValid || (remainingBlocks == 0 && badges >= 9)} onClick={awake}>
+            disabled={isSubmitting || !isValid || (remainingBlocks == 0 && badges >= badgesRequired)} onClick={awake}>
             Mint NFT
           </Button>
         </>
@@ -55,11 +57,11 @@ export const MaginkForm = ({ awake, isAwake, remainingBlocks, runtimeError, badg
       <div className="group">
         {account && isAwake && (
           <>
-            <InkFacts badges={badges} />
+            <InkFacts badges={badges} lessons={lessons} />
             <br />
             <Button
               type="submit"
-              disabled={isSubmitting || !isValid || (remainingBlocks != 0 && !isFirtsClaim) || badges >= 9}
+              disabled={isSubmitting || !isValid || (remainingBlocks != 0 && !isFirtsClaim) || badges >= badgesRequired}
             >
               Claim badge
             </Button>
@@ -71,13 +73,13 @@ export const MaginkForm = ({ awake, isAwake, remainingBlocks, runtimeError, badg
           </Button>
         )}
       </div>
-      {remainingBlocks != 0 && isAwake && badges <= 9 && !isFirtsClaim && (
+      {remainingBlocks != 0 && isAwake && badges <= badgesRequired && !isFirtsClaim && (
         <div className="text-xs text-left mb-2 text-gray-200">
           Claim a new badge after {remainingBlocks} blocks
         </div>
       )}
       {account && isAwake && (
-        <Gallery level={badges} />
+        <Gallery level={badges} required={badgesRequired} />
       )}
       {runtimeError && magink && (
         <div className="text-xs text-left mb-2 text-red-500">
@@ -9,9 +9,10 @@ import { hasAny, pickError } from 'useink/utils';
 import { useEffect, useState } from 'react';
 import { decodeError } from 'useink/core';
 import { useBlockHeader, useWallet } from 'useink';
+import { Lesson } from '../types';
 
 export const FormContainer = () => {
-  const { magink, start, getRemaining, getRemainingFor, getBadgesFor } = useMaginkContract();
+  const { magink, start, getRemaining, getRemainingFor, getBadgesFor, getBadgesRequired, getLessons } = useMaginkContract();
   const submitFn = useSubmitHandler();
   const { account } = useWallet();
   const { showConnectWallet, setShowConnectWallet } = useUI();
@@ -22,6 +23,8 @@ export const FormContainer = () => {
   const [isMinting, setIsMinting] = useState(false);
   const [remainingBlocks, setRemainingBlocks] = useState<number>(0);
   const [badges, setBadges] = useState<number>(0);
+  const [badgesRequired, setBadgesRequired] = useState<number>(0);
+  const [lessons, setLessons] = useState<Lesson[]>([]);
   const block = useBlockHeader();
 
   var runtimeError: any; // TODO check this
@@ -30,7 +33,23 @@ export const FormContainer = () => {
     checkBadges();
   }, [block]);
 
+  // the lessons and the mint threshold are set by the contract owner
+  const readLessons = async () => {
+    const required = await getBadgesRequired?.send([], { defaultCaller: true });
+    if (required?.ok) {
+      setBadgesRequired(required.value.decoded);
+    }
+
+    const lessons = await getLessons?.send([], { defaultCaller: true });
+    if (lessons?.ok) {
+      setLessons(lessons.value.decoded);
+    }
+
+    return required?.ok ? required.value.decoded : badgesRequired;
+  };
+
   const checkBadges = async () => {
+    await readLessons();
     if (!isAwake) return;
     //get remaining blocks until next claim
     const remaining = await getRemainingFor?.send([account?.address], { defaultCaller: true });
@@ -53,13 +72,14 @@ export const FormContainer = () => {
 
   const readBadges = async () => {
     console.log('##### getBadgesFor add', account?.address);
+    const required = await readLessons();
     const badges = await getBadgesFor?.send([account?.address], { defaultCaller: true });
     console.log('##### getBadgesFor value', badges?.ok && badges.value.decoded);
     if (badges?.ok && badges.value.decoded) {
       setBadges(badges.value.decoded);
       if (badges.value.decoded == 0) {
         startMagink();
-      } else if (badges.value.decoded == 9) {
+      } else if (badges.value.decoded >= required) {
         mintMagink();
       } else {
         setIsAwake(true);
@@ -69,10 +89,9 @@ export const FormContainer = () => {
 
   const startMagink = async () => {
     console.log('startMagink');
-    const startArgs = [initialValues.blocksToLive];
     const options = undefined;
     setIsStarting(true);
-    start?.signAndSend(startArgs, options, (result: any, _api: any, error: any) => {
+    start?.signAndSend([], options, (result: any, _api: any, error: any) => {
       if (error) {
         console.error(JSON.stringify(error));
       }
@@ -144,6 +163,8 @@ export const FormContainer = () => {
                           awake={readBadges}
                           isAwake={isAwake}
                           badges={badges}
+                          badgesRequired={badgesRequired}
+                          lessons={lessons}
                           remainingBlocks={remainingBlocks}
                           runtimeError={runtimeError}
                         />
@@ -12,9 +12,10 @@ import book from "../assets/book.png";
 
 type Props = {
     level: number;
+    required: number;
 };
 
-export const Gallery = ({ level }: Props) => {
+export const Gallery = ({ level, required }: Props) => {
     const [gallery, setGallery] = useState<number>(0);
 
     const fullGallery: Array<string> = [candle, book, frog, pot, crow, crystalball, crystals, potionblue, hat];
@@ -33,7 +34,7 @@ export const Gallery = ({ level }: Props) => {
         <div>
             <br/>
             <hr/>
-            {gallery == 9 ? <h3 className="animate-pulse text-xs text-right mb-2 text-violet-500">Congratulations! You have completed the Swanky Magink! School</h3>
+            {required > 0 && gallery >= required ? <h3 className="animate-pulse text-xs text-right mb-2 text-violet-500">Congratulations! You have completed the Swanky Magink! School</h3>
             : <p className="text-xs text-right mb-2 text-violet-500">You earned {gallery} badges</p>}
             <div className="flex flex-wrap">
                 <GalleryItems />
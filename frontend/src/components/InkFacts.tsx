@@ -1,13 +1,18 @@
 import { astarFacts } from '../const';
+import { Lesson } from '../types';
 
 interface Props {
     badges: number;
+    lessons: Lesson[];
   }
 
-const InkFacts = ( {badges}: Props) => {
+const InkFacts = ( {badges, lessons}: Props) => {
+  const lesson = lessons[badges];
+
   return (
     <div>
-      <p dangerouslySetInnerHTML={{ __html: astarFacts[badges] }} />
+      {lesson && <h3>{lesson.title}</h3>}
+      <p dangerouslySetInnerHTML={{ __html: astarFacts[badges] ?? '' }} />
     </div>
   );
 };
@@ -3,6 +3,7 @@ import { useContract, DryRun, useDryRun, useTx, Tx, useCall, Call, ChainContract
 import { CONTRACT_ADDRESS } from "../const";
 import metadata from "../metadata.json";
 import { useTxNotifications } from "useink/notifications";
+import { Lesson } from "../types";
 
 interface MaginkContractState {
   magink?: ChainContract; 
@@ -16,6 +17,8 @@ interface MaginkContractState {
   getRemainingFor?: Call<number>;
   getBadges?: Call<number>;
   getBadgesFor?: Call<number>;
+  getBadgesRequired?: Call<number>;
+  getLessons?: Call<Lesson[]>;
 }
 
 export const MaginkContractContext = createContext<MaginkContractState>({});
@@ -32,11 +35,13 @@ export function MaginkContractProvider({ children }: PropsWithChildren) {
   const getBadges = useCall<number>(magink, 'getBadges');
   const getBadgesFor = useCall<number>(magink, 'getBadgesFor');
   const getRemainingFor = useCall<number>(magink, 'getRemainingFor');
+  const getBadgesRequired = useCall<number>(magink, 'getBadgesRequired');
+  const getLessons = useCall<Lesson[]>(magink, 'getLessons');
   useTxNotifications(claim);
   useTxNotifications(start);
 
   return (
-    <MaginkContractContext.Provider value={{ magink, startDryRun, claimDryRun, mintDryRun, start, claim, mint, getRemaining, getRemainingFor, getBadges, getBadgesFor }}>
+    <MaginkContractContext.Provider value={{ magink, startDryRun, claimDryRun, mintDryRun, start, claim, mint, getRemaining, getRemainingFor, getBadges, getBadgesFor, getBadgesRequired, getLessons }}>
       {children}
     </MaginkContractContext.Provider>
   );
@@ -0,0 +1,6 @@
+export interface Lesson {
+  id: number;
+  contentHash: string;
+  title: string;
+  retired: boolean;
+}
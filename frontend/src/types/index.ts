@@ -1,2 +1,3 @@
 export * from './Balances';
+export * from './Lesson';
 export * from './UI';